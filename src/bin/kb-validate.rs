@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Проверка KB перед выпуском: `cargo run --bin kb-validate -- [каталог kb]`.
+// Загружает каждый *.ttl пакетом, проверяет правила формы, манифест, астро-KB,
+// правила вывода и наличие ресурсов, на которые ссылается код.
+// Код выхода 1 — есть нарушения, которые нельзя выпускать.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use noemafeed_mobile::core::astro;
+use noemafeed_mobile::engine;
+use noemafeed_mobile::knowledge::validate::{self, Severity};
+use noemafeed_mobile::knowledge::{manifest, packs, rules};
+use oxigraph::store::Store;
+
+/// Все *.ttl каталога рекурсивно, в порядке путей
+fn ttl_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            ttl_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "ttl") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(())
+}
+
+/// Имя пакета — путь относительно каталога kb с разделителем `/`
+fn pack_name(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn run(root: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let store = Store::new()?;
+    let mut errors = 0;
+
+    let mut files = Vec::new();
+    ttl_files(root, &mut files)?;
+    for path in &files {
+        let name = pack_name(root, path);
+        if let Err(e) = packs::load_pack(&store, &name, &fs::read(path)?) {
+            println!("ОШИБКА {}: {}", name, e);
+            errors += 1;
+        }
+    }
+    println!("Загружено пакетов: {} из {}", files.len() - errors, files.len());
+
+    // === Правила формы ===
+    for v in validate::validate(&store)? {
+        let value = v.value.map(|value| format!(" → {}", value)).unwrap_or_default();
+        let level = match v.severity {
+            Severity::Violation => {
+                errors += 1;
+                "ОШИБКА"
+            }
+            Severity::Warning => "ВНИМАНИЕ",
+        };
+        println!("{} {}: {}{} — {} [{}]", level, v.file, v.subject, value, v.message, v.shape);
+    }
+
+    // === Манифест: версии и «10 баз» ===
+    for problem in manifest::build(&store)?.problems() {
+        println!("ОШИБКА {}", problem);
+        errors += 1;
+    }
+
+    // === Астро-KB: темпераменты и календарь знаков ===
+    if let Err(e) = astro::verify(&store) {
+        println!("ОШИБКА subjects/astro-profiles.ttl: {}", e);
+        errors += 1;
+    }
+
+    // === Правила вывода: тела выполняются, вывод сходится ===
+    match rules::infer(&store) {
+        Ok((_, report)) => {
+            for run in &report.rules {
+                println!("Правило {}: выведено {}", run.rule, run.derived.len());
+            }
+            if !report.fixpoint {
+                println!("ВНИМАНИЕ правила не сошлись за {} проходов", rules::MAX_ITERATIONS);
+            }
+        }
+        Err(e) => {
+            println!("ОШИБКА правило {}", e);
+            errors += 1;
+        }
+    }
+
+    // === Ресурсы, на которые ссылается код ядра ===
+    if let Err(e) = engine::verify_references(&store) {
+        println!("ОШИБКА {}", e);
+        errors += 1;
+    }
+    Ok(errors)
+}
+
+fn main() -> ExitCode {
+    let root = std::env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("kb"));
+    match run(&root) {
+        Ok(0) => {
+            println!("KB корректна");
+            ExitCode::SUCCESS
+        }
+        Ok(errors) => {
+            println!("Нарушений: {}", errors);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{}: {}", root.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Исторические аналоги: события индекса с тем же типом возмущения, акторами или циклом,
+// ранжированные по структурному сходству. Без аналогов событие «потенциально уникально».
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::IndexedEvent;
+use crate::iri::Iri;
+use crate::trace::Trace;
+
+// === Веса структурного сходства ===
+
+const W_ACTION: f32 = 0.5; // общий тип возмущения
+const W_ACTORS: f32 = 0.25; // доля общих акторов (коэффициент Жаккара)
+const W_CYCLE: f32 = 0.25; // общий цикл
+const W_EXPLICIT: f32 = 0.25; // явная связь temporal:analogousTo
+
+/// Число аналогов в результате по умолчанию
+pub const DEFAULT_MAX_ANALOGS: usize = 3;
+
+/// Исторический аналог события
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Analog {
+    pub event: Iri,
+    pub label: Option<String>,
+    pub occurred_at: Option<String>,
+    pub similarity: f32,     // 0..1
+    pub shared: Vec<String>, // общие признаки: «pert:…», «актор wd:…», «цикл …», «temporal:analogousTo»
+}
+
+/// Аналоги события; `potentially_unique` — в индексе нет ни одного аналога
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AnalogReport {
+    pub analogs: Vec<Analog>,
+    pub potentially_unique: bool,
+}
+
+fn jaccard(a: &BTreeSet<Iri>, b: &BTreeSet<Iri>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        0.0
+    } else {
+        a.intersection(b).count() as f32 / union as f32
+    }
+}
+
+/// Сходство кандидата с событием и общие признаки; `None` — общего нет
+fn similarity(event: &IndexedEvent, candidate: &IndexedEvent) -> Option<(f32, Vec<String>)> {
+    let mut score = 0.0;
+    let mut shared = Vec::new();
+
+    let actions: Vec<&Iri> = event.actions.intersection(&candidate.actions).collect();
+    if !actions.is_empty() {
+        score += W_ACTION;
+        shared.extend(actions.iter().map(|a| a.curie()));
+    }
+    let actors: Vec<&Iri> = event.actors.intersection(&candidate.actors).collect();
+    if !actors.is_empty() {
+        score += W_ACTORS * jaccard(&event.actors, &candidate.actors);
+        shared.extend(actors.iter().map(|a| format!("актор {}", a)));
+    }
+    let cycles: Vec<&Iri> = event.cycles.intersection(&candidate.cycles).collect();
+    if !cycles.is_empty() {
+        score += W_CYCLE;
+        shared.extend(cycles.iter().map(|c| format!("цикл {}", c)));
+    }
+    let explicit = match (&event.id, &candidate.id) {
+        (Some(id), Some(other)) => event.analogous_to.contains(other) || candidate.analogous_to.contains(id),
+        _ => false,
+    };
+    if explicit {
+        score += W_EXPLICIT;
+        shared.push("temporal:analogousTo".to_string());
+    }
+
+    (!shared.is_empty()).then(|| (f32::min(score, 1.0), shared))
+}
+
+/// Аналоги события среди событий индекса, от самого похожего; при равенстве — более поздние
+pub fn find(
+    event: &IndexedEvent,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    limit: usize,
+    trace: Option<&mut Trace>,
+) -> AnalogReport {
+    let mut analogs: Vec<Analog> = index
+        .values()
+        .filter(|candidate| candidate.id.is_some() && candidate.id != event.id)
+        .filter_map(|candidate| {
+            let (similarity, shared) = similarity(event, candidate)?;
+            Some(Analog {
+                event: candidate.id.clone()?,
+                label: candidate.label.clone(),
+                occurred_at: candidate.occurred_at.clone(),
+                similarity,
+                shared,
+            })
+        })
+        .collect();
+    analogs.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| b.occurred_at.cmp(&a.occurred_at))
+            .then_with(|| a.event.cmp(&b.event))
+    });
+    let potentially_unique = analogs.is_empty();
+    analogs.truncate(limit);
+
+    if let Some(trace) = trace {
+        for analog in &analogs {
+            trace.scoring_step(format!(
+                "Аналог {}: сходство {:.2} ({})",
+                analog.event,
+                analog.similarity,
+                analog.shared.join(", ")
+            ));
+        }
+        if potentially_unique {
+            trace.scoring_step("Аналогов в контекстуальном индексе нет: событие потенциально уникально");
+        }
+    }
+    AnalogReport {
+        potentially_unique,
+        analogs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexed(id: &str, date: &str, actions: &[&str], actors: &[&str]) -> (Iri, IndexedEvent) {
+        let id = Iri::from_curie(id);
+        let event = IndexedEvent {
+            id: Some(id.clone()),
+            occurred_at: Some(date.to_string()),
+            actions: actions.iter().map(|a| Iri::from_curie(a)).collect(),
+            actors: actors.iter().map(|a| Iri::from_curie(a)).collect(),
+            ..Default::default()
+        };
+        (id, event)
+    }
+
+    #[test]
+    fn uniqueness_is_decided_before_truncation() {
+        let index = BTreeMap::from([
+            indexed("temporal:Old", "2001-01-01", &["pert:Leak"], &[]),
+            indexed("temporal:New", "2020-01-01", &["pert:Leak"], &[]),
+            indexed("temporal:Close", "2010-01-01", &["pert:Leak"], &["wd:Q1"]),
+            indexed("temporal:Other", "2015-01-01", &["pert:Ban"], &[]),
+        ]);
+        let news = IndexedEvent {
+            actions: BTreeSet::from([Iri::from_curie("pert:Leak")]),
+            actors: BTreeSet::from([Iri::from_curie("wd:Q1")]),
+            ..Default::default()
+        };
+
+        // Самый похожий — с общим актором; при равном сходстве — более поздний
+        let report = find(&news, &index, 10, None);
+        let ranked: Vec<&str> = report.analogs.iter().map(|a| a.event.local_name()).collect();
+        assert_eq!(ranked, ["Close", "New", "Old"]);
+
+        // Ни одного аналога в ответе, но в индексе они есть
+        let report = find(&news, &index, 0, None);
+        assert!(report.analogs.is_empty());
+        assert!(!report.potentially_unique);
+
+        let alone = IndexedEvent {
+            actions: BTreeSet::from([Iri::from_curie("pert:Censorship")]),
+            ..Default::default()
+        };
+        assert!(find(&alone, &index, 10, None).potentially_unique);
+    }
+}
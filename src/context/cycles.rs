@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Циклы контекстуального индекса: ресурсы `temporal:Cycle` и события, входящие в них
+// (`temporal:partOfCycle`). По датам событий оцениваются период и разброс цикла
+// и место нового события в нём: сколько прошло с прошлого события и когда ждать следующего.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+
+use crate::context::analogs::AnalogReport;
+use crate::context::IndexedEvent;
+use crate::iri::Iri;
+use crate::knowledge::events::parse_date;
+use crate::sparql;
+use crate::trace::Trace;
+
+/// Средняя длина григорианского года в днях
+const DAYS_PER_YEAR: f64 = 365.2425;
+
+const CYCLES_QUERY: &str = r#"
+SELECT ?cycle (SAMPLE(?l) AS ?label) WHERE {
+    ?cycle a temporal:Cycle .
+    OPTIONAL { ?cycle rdfs:label ?l }
+}
+GROUP BY ?cycle
+ORDER BY ?cycle
+"#;
+
+/// Событие цикла
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CycleMember {
+    pub event: Iri,
+    pub label: Option<String>,
+    pub occurred_at: Option<String>,
+}
+
+/// Цикл с оценкой периода; интервалы, период и разброс — в годах
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Cycle {
+    pub id: Iri,
+    pub label: Option<String>,
+    pub members: Vec<CycleMember>, // от самого раннего; события без даты — в конце
+    pub intervals: Vec<f64>,       // между соседними датированными событиями
+    pub period: Option<f64>,       // среднее интервалов; None — меньше двух датированных событий
+    pub std_dev: Option<f64>,      // выборочное отклонение; None — меньше двух интервалов
+}
+
+/// Место события в цикле
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CyclePosition {
+    pub cycle: Cycle,
+    pub via_analog: bool,               // событие не в цикле: цикл взят у его аналогов
+    pub previous: Option<CycleMember>,  // последнее событие цикла до нового
+    pub elapsed: Option<f64>,           // лет с предыдущего события
+    pub phase: Option<f64>,             // elapsed / period: около 1 — цикл «созрел»
+    pub next_expected: Option<String>,  // дата: предыдущее событие + период
+}
+
+/// Номер дня от 1970-01-01 по пролептическому григорианскому календарю
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = i64::from(year) - i64::from(month <= 2);
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Дата `ГГГГ-ММ-ДД` по номеру дня от 1970-01-01
+fn civil_from_days(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn day_number(date: &str) -> Option<i64> {
+    parse_date(date).map(|(y, m, d)| days_from_civil(y, m, d))
+}
+
+/// Циклы KB с событиями из индекса и оценкой периода
+pub fn load(store: &Store, index: &BTreeMap<Iri, IndexedEvent>) -> Result<BTreeMap<Iri, Cycle>, String> {
+    let mut cycles = BTreeMap::new();
+    for row in sparql::select(store, CYCLES_QUERY, &[])? {
+        let Some(id) = sparql::iri(&row, "cycle") else {
+            continue;
+        };
+        let mut members: Vec<(Option<i64>, CycleMember)> = index
+            .values()
+            .filter(|e| e.cycles.contains(&id))
+            .filter_map(|e| {
+                let member = CycleMember {
+                    event: e.id.clone()?,
+                    label: e.label.clone(),
+                    occurred_at: e.occurred_at.clone(),
+                };
+                Some((e.occurred_at.as_deref().and_then(day_number), member))
+            })
+            .collect();
+        members.sort_by(|(a, x), (b, y)| (a.is_none(), a, &x.event).cmp(&(b.is_none(), b, &y.event)));
+
+        let days: Vec<i64> = members.iter().filter_map(|(day, _)| *day).collect();
+        let intervals: Vec<f64> = days.windows(2).map(|w| (w[1] - w[0]) as f64 / DAYS_PER_YEAR).collect();
+        let period = (!intervals.is_empty()).then(|| intervals.iter().sum::<f64>() / intervals.len() as f64);
+        let std_dev = period.filter(|_| intervals.len() >= 2).map(|mean| {
+            let variance = intervals.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (intervals.len() - 1) as f64;
+            variance.sqrt()
+        });
+        cycles.insert(
+            id.clone(),
+            Cycle {
+                id,
+                label: sparql::literal(&row, "label"),
+                members: members.into_iter().map(|(_, member)| member).collect(),
+                intervals,
+                period,
+                std_dev,
+            },
+        );
+    }
+    Ok(cycles)
+}
+
+/// Место события в цикле. Предыдущее — последнее датированное событие цикла не позже `date`
+/// (без даты — последнее вообще); само событие не считается.
+pub fn position(cycle: &Cycle, event: Option<&Iri>, date: Option<&str>, via_analog: bool) -> CyclePosition {
+    let day = date.and_then(day_number);
+    let previous = cycle
+        .members
+        .iter()
+        .filter(|m| Some(&m.event) != event)
+        .filter_map(|m| Some((m.occurred_at.as_deref().and_then(day_number)?, m)))
+        .filter(|(d, _)| day.is_none_or(|day| *d <= day))
+        .max_by_key(|(d, _)| *d);
+    let elapsed = previous
+        .zip(day)
+        .map(|((prev, _), day)| (day - prev) as f64 / DAYS_PER_YEAR);
+    CyclePosition {
+        cycle: cycle.clone(),
+        via_analog,
+        previous: previous.map(|(_, m)| m.clone()),
+        elapsed,
+        phase: elapsed.zip(cycle.period).map(|(elapsed, period)| elapsed / period),
+        next_expected: previous
+            .zip(cycle.period)
+            .map(|((prev, _), period)| civil_from_days(prev + (period * DAYS_PER_YEAR).round() as i64)),
+    }
+}
+
+/// Циклы события: его собственные, а если оно ни в одном цикле — циклы его аналогов.
+/// `cycles` — циклы KB, загруженные `load` по тому же индексу
+pub fn locate(
+    cycles: &BTreeMap<Iri, Cycle>,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    event: &IndexedEvent,
+    analogs: &AnalogReport,
+    trace: Option<&mut Trace>,
+) -> Result<Vec<CyclePosition>, String> {
+    let (ids, via_analog): (BTreeSet<Iri>, bool) = if event.cycles.is_empty() {
+        let ids = analogs
+            .analogs
+            .iter()
+            .filter_map(|a| index.get(&a.event))
+            .flat_map(|e| e.cycles.iter().cloned())
+            .collect();
+        (ids, true)
+    } else {
+        (event.cycles.clone(), false)
+    };
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let positions: Vec<CyclePosition> = ids
+        .iter()
+        .filter_map(|id| cycles.get(id))
+        .map(|cycle| position(cycle, event.id.as_ref(), event.occurred_at.as_deref(), via_analog))
+        .collect();
+    if let Some(trace) = trace {
+        for p in &positions {
+            trace.scoring_step(format!(
+                "Цикл {}{}: {} событий, период {}",
+                p.cycle.id,
+                if p.via_analog { " (по аналогам)" } else { "" },
+                p.cycle.members.len(),
+                p.cycle.period.map_or("не оценён".to_string(), |period| format!("{:.1} г.", period))
+            ));
+        }
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context;
+    use crate::core::astro::days_in_month;
+    use crate::knowledge::packs;
+
+    #[test]
+    fn civil_days_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(0), "1970-01-01");
+        for year in [1600, 1899, 1900, 1999, 2000, 2004, 2023, 2024, 2100, 2400] {
+            for month in 1..=12 {
+                for day in [1, days_in_month(year, month)] {
+                    let days = days_from_civil(year, month, day);
+                    assert_eq!(civil_from_days(days), format!("{:04}-{:02}-{:02}", year, month, day));
+                }
+            }
+        }
+        // 29 февраля — только в високосные годы; 1900 и 2100 не високосные
+        assert_eq!(days_from_civil(2000, 3, 1) - days_from_civil(2000, 2, 28), 2);
+        assert_eq!(days_from_civil(1900, 3, 1) - days_from_civil(1900, 2, 28), 1);
+        assert_eq!(days_from_civil(2100, 3, 1) - days_from_civil(2100, 2, 28), 1);
+    }
+
+    #[test]
+    fn fixed_interval_period() {
+        // Три события с шагом ровно в четыре григорианских года (1461 день)
+        let store = Store::new().unwrap();
+        let ttl = r#"
+            @prefix temporal: <http://onto16.org/temporal/> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            temporal:TestCycle a temporal:Cycle ; rdfs:label "Тестовый цикл" .
+            temporal:A a temporal:Event ; temporal:occurredAt "2000-01-01"^^xsd:date ; temporal:partOfCycle temporal:TestCycle .
+            temporal:B a temporal:Event ; temporal:occurredAt "2004-01-01"^^xsd:date ; temporal:partOfCycle temporal:TestCycle .
+            temporal:C a temporal:Event ; temporal:occurredAt "2008-01-01"^^xsd:date ; temporal:partOfCycle temporal:TestCycle .
+        "#;
+        packs::load_pack(&store, "test/cycle.ttl", ttl.as_bytes()).unwrap();
+        let index = context::index(&store).unwrap();
+        let cycles = load(&store, &index).unwrap();
+        let cycle = &cycles[&Iri::from_curie("temporal:TestCycle")];
+
+        let members: Vec<&str> = cycle.members.iter().map(|m| m.event.local_name()).collect();
+        assert_eq!(members, ["A", "B", "C"]);
+        let period = cycle.period.unwrap();
+        assert!((period - 1461.0 / DAYS_PER_YEAR).abs() < 1e-9);
+        assert_eq!(cycle.std_dev, Some(0.0));
+
+        let position = position(cycle, None, Some("2010-01-01T09:00:00Z"), false);
+        assert_eq!(position.previous.map(|m| m.event.local_name().to_string()).as_deref(), Some("C"));
+        assert_eq!(position.next_expected.as_deref(), Some("2012-01-01"));
+        assert!((position.phase.unwrap() - 0.5).abs() < 0.01);
+    }
+}
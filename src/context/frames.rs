@@ -0,0 +1,534 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Причинные фреймы контекстуального индекса.
+// Фрейм следствий: цепочки `temporal:ledTo` вперёд от события с ограничением глубины
+// и проекция уровней ФУС, которые вероятнее всего будут затронуты дальше.
+// Фрейм причин: `temporal:triggeredBy` и обратный `temporal:ledTo` назад от события,
+// циклы и структурные предпосылки из KB — упорядоченное объяснение «как мы сюда пришли».
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+
+use crate::context::cycles::Cycle;
+use crate::context::IndexedEvent;
+use crate::core::fus::{FusLevel, InvariantRegistry};
+use crate::iri::Iri;
+use crate::sparql;
+
+/// Глубина обхода по умолчанию и наибольшая допустимая
+pub const DEFAULT_DEPTH: usize = 3;
+pub const MAX_DEPTH: usize = 10;
+
+/// Событие во фрейме
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FrameNode {
+    pub event: Iri,
+    pub label: Option<String>,
+    pub occurred_at: Option<String>,
+    pub perturbations: Vec<Iri>,
+    pub levels: Vec<FusLevel>, // уровни затронутых инвариантов, от самого глубокого
+    pub depth: usize,          // шагов от исходного события
+    pub children: Vec<FrameNode>,
+}
+
+/// Уровень ФУС в проекции следствий
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LevelProjection {
+    pub level: FusLevel,
+    pub likelihood: f32,     // доля веса; ближние следствия весят больше (1 / глубина)
+    pub invariants: Vec<Iri>, // инварианты уровня, затронутые следствиями
+}
+
+/// Фрейм следствий
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EffectFrame {
+    pub anchor: Iri,               // событие индекса, от которого строится фрейм
+    pub via_analog: bool,          // исходная новость не в индексе: фрейм построен от её аналога
+    pub root: FrameNode,
+    pub projection: Vec<LevelProjection>, // от самого вероятного уровня
+    pub truncated: bool,           // обход остановлен ограничением глубины
+}
+
+/// Узел фрейма по событию индекса, без потомков
+fn node(event: &Iri, index: &BTreeMap<Iri, IndexedEvent>, invariants: &InvariantRegistry, depth: usize) -> FrameNode {
+    let indexed = index.get(event);
+    let mut levels: Vec<FusLevel> = indexed
+        .into_iter()
+        .flat_map(|e| &e.invariants)
+        .filter_map(|inv| invariants.get(inv).map(|inv| inv.level))
+        .collect();
+    levels.sort_by_key(FusLevel::depth);
+    levels.dedup();
+    FrameNode {
+        event: event.clone(),
+        label: indexed.and_then(|e| e.label.clone()),
+        occurred_at: indexed.and_then(|e| e.occurred_at.clone()),
+        perturbations: indexed.map(|e| e.actions.iter().cloned().collect()).unwrap_or_default(),
+        levels,
+        depth,
+        children: Vec::new(),
+    }
+}
+
+/// Обход `temporal:ledTo` в глубину; событие, уже стоящее на пути, не повторяется
+fn expand(
+    parent: &mut FrameNode,
+    path: &mut Vec<Iri>,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    invariants: &InvariantRegistry,
+    max_depth: usize,
+    truncated: &mut bool,
+) {
+    let Some(next) = index.get(&parent.event).map(|e| &e.led_to) else {
+        return;
+    };
+    if next.is_empty() {
+        return;
+    }
+    if parent.depth == max_depth {
+        *truncated = true;
+        return;
+    }
+    for event in next {
+        if path.contains(event) {
+            continue;
+        }
+        let mut child = node(event, index, invariants, parent.depth + 1);
+        path.push(event.clone());
+        expand(&mut child, path, index, invariants, max_depth, truncated);
+        path.pop();
+        parent.children.push(child);
+    }
+    parent.children.sort_by(|a, b| (&a.occurred_at, &a.event).cmp(&(&b.occurred_at, &b.event)));
+}
+
+/// Проекция уровней по следствиям: вес инварианта — 1 / глубина узла
+fn project(root: &FrameNode, index: &BTreeMap<Iri, IndexedEvent>, invariants: &InvariantRegistry) -> Vec<LevelProjection> {
+    let mut weights: Vec<(FusLevel, f32, Vec<Iri>)> = Vec::new();
+    let mut stack: Vec<&FrameNode> = root.children.iter().collect();
+    while let Some(node) = stack.pop() {
+        stack.extend(node.children.iter());
+        for id in index.get(&node.event).into_iter().flat_map(|e| &e.invariants) {
+            let Some(inv) = invariants.get(id) else {
+                continue;
+            };
+            let weight = 1.0 / node.depth as f32;
+            match weights.iter_mut().find(|(level, _, _)| *level == inv.level) {
+                Some((_, w, ids)) => {
+                    *w += weight;
+                    if !ids.contains(id) {
+                        ids.push(id.clone());
+                    }
+                }
+                None => weights.push((inv.level, weight, vec![id.clone()])),
+            }
+        }
+    }
+
+    let total: f32 = weights.iter().map(|(_, w, _)| w).sum();
+    let mut projection: Vec<LevelProjection> = weights
+        .into_iter()
+        .map(|(level, weight, mut ids)| {
+            ids.sort();
+            LevelProjection {
+                level,
+                likelihood: weight / total,
+                invariants: ids,
+            }
+        })
+        .collect();
+    projection.sort_by(|a, b| {
+        b.likelihood
+            .total_cmp(&a.likelihood)
+            .then_with(|| a.level.depth().cmp(&b.level.depth()))
+    });
+    projection
+}
+
+/// Фрейм следствий события индекса `anchor` с глубиной не больше `max_depth` (≤ `MAX_DEPTH`)
+pub fn effect_frame(
+    anchor: &Iri,
+    via_analog: bool,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    invariants: &InvariantRegistry,
+    max_depth: usize,
+) -> Result<EffectFrame, String> {
+    if !index.contains_key(anchor) {
+        return Err(format!("{}: события нет в контекстуальном индексе", anchor));
+    }
+    let mut root = node(anchor, index, invariants, 0);
+    let mut truncated = false;
+    let mut path = vec![anchor.clone()];
+    expand(&mut root, &mut path, index, invariants, max_depth.min(MAX_DEPTH), &mut truncated);
+    Ok(EffectFrame {
+        anchor: anchor.clone(),
+        via_analog,
+        projection: project(&root, index, invariants),
+        root,
+        truncated,
+    })
+}
+
+// === Фрейм причин ===
+
+/// Предшествующее событие во фрейме причин
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CauseStep {
+    pub event: Iri,
+    pub label: Option<String>,
+    pub occurred_at: Option<String>,
+    pub perturbations: Vec<Iri>,
+    pub relation: Iri, // temporal:triggeredBy или temporal:ledTo
+    pub caused: Iri,   // событие, к которому привёл шаг
+    pub depth: usize,  // шагов до исходного события
+}
+
+/// Структурная предпосылка из KB: актор, тип возмущения или вывод правила
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Precondition {
+    pub event: Iri,
+    pub subject: Iri,
+    pub property: Iri, // rdf:type, fus:activeInvariant, pert:leadsTo, pert:affectsInvariant, rule:flagsInvariant
+    pub value: Iri,
+    pub text: String,
+}
+
+/// Фрейм причин
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CauseFrame {
+    pub anchor: Iri,
+    pub via_analog: bool,
+    pub steps: Vec<CauseStep>, // от самого раннего
+    pub cycles: Vec<Cycle>, // циклы исходного события и его предшественников
+    pub preconditions: Vec<Precondition>,
+    pub explanation: Vec<String>, // циклы, предпосылки, цепочка событий — по порядку
+    pub truncated: bool,
+}
+
+const PRECONDITIONS_QUERY: &str = r#"
+SELECT ?event ?subject ?property ?value (SAMPLE(?sl) AS ?subject_label) (SAMPLE(?vl) AS ?value_label) WHERE {
+    {
+        ?event temporal:involvedActor ?subject .
+        ?subject a ?value .
+        ?value rdfs:subClassOf fus:FUS_Subject .
+        FILTER NOT EXISTS {
+            ?subject a ?narrower .
+            ?narrower rdfs:subClassOf ?value .
+            FILTER(?narrower != ?value)
+        }
+        BIND(rdf:type AS ?property)
+    } UNION {
+        ?event temporal:involvedActor ?subject .
+        ?subject fus:activeInvariant ?value .
+        BIND(fus:activeInvariant AS ?property)
+    } UNION {
+        ?event temporal:describesAction ?subject .
+        ?subject a pert:Trigger ;
+            pert:leadsTo ?value .
+        BIND(pert:leadsTo AS ?property)
+    } UNION {
+        ?event temporal:describesAction ?action .
+        OPTIONAL { ?action a pert:Trigger ; pert:leadsTo ?led }
+        BIND(COALESCE(?led, ?action) AS ?subject)
+        ?subject pert:affectsInvariant ?value .
+        BIND(pert:affectsInvariant AS ?property)
+    } UNION {
+        ?event rule:flagsInvariant ?value .
+        BIND(?event AS ?subject)
+        BIND(rule:flagsInvariant AS ?property)
+    }
+    OPTIONAL { ?subject rdfs:label ?sl }
+    OPTIONAL { ?value rdfs:label ?vl }
+}
+GROUP BY ?event ?subject ?property ?value
+ORDER BY ?property ?subject ?value
+"#;
+
+fn named(label: Option<String>, iri: &Iri) -> String {
+    match label {
+        Some(label) => format!("{} ({})", label, iri),
+        None => iri.to_string(),
+    }
+}
+
+/// Предпосылки события из KB
+fn preconditions(store: &Store, event: &Iri) -> Result<Vec<Precondition>, String> {
+    let mut result = Vec::new();
+    for row in sparql::select(store, PRECONDITIONS_QUERY, &[("event", event.term())])? {
+        let (Some(subject), Some(property), Some(value)) =
+            (sparql::iri(&row, "subject"), sparql::iri(&row, "property"), sparql::iri(&row, "value"))
+        else {
+            continue;
+        };
+        let s = named(sparql::literal(&row, "subject_label"), &subject);
+        let v = named(sparql::literal(&row, "value_label"), &value);
+        let text = match property.curie().as_str() {
+            "rdf:type" => format!("Актор {} — {}", s, v),
+            "fus:activeInvariant" => format!("Актор {}: активный инвариант {}", s, v),
+            "pert:leadsTo" => format!("Триггер {} ведёт к возмущению {}", s, v),
+            "pert:affectsInvariant" => format!("Возмущение {} затрагивает {}", s, v),
+            _ => format!("Правило KB отметило: {}", v),
+        };
+        result.push(Precondition {
+            event: event.clone(),
+            subject,
+            property,
+            value,
+            text,
+        });
+    }
+    Ok(result)
+}
+
+/// Непосредственные предшественники: триггеры события и события, которые к нему привели
+fn predecessors(event: &Iri, index: &BTreeMap<Iri, IndexedEvent>) -> Vec<(Iri, Iri)> {
+    let triggered_by = Iri::from_curie("temporal:triggeredBy");
+    let led_to = Iri::from_curie("temporal:ledTo");
+    let mut result: Vec<(Iri, Iri)> = index
+        .get(event)
+        .into_iter()
+        .flat_map(|e| &e.triggered_by)
+        .map(|cause| (cause.clone(), triggered_by.clone()))
+        .collect();
+    for (id, candidate) in index {
+        if candidate.led_to.contains(event) && !result.iter().any(|(cause, _)| cause == id) {
+            result.push((id.clone(), led_to.clone()));
+        }
+    }
+    result
+}
+
+/// Фрейм причин события индекса `anchor` с глубиной не больше `max_depth` (≤ `MAX_DEPTH`).
+/// Обход в ширину: предшественник, достижимый несколькими путями, входит один раз — по кратчайшему.
+/// `known_cycles` — циклы KB, загруженные `cycles::load` по тому же индексу
+pub fn cause_frame(
+    store: &Store,
+    anchor: &Iri,
+    via_analog: bool,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    known_cycles: &BTreeMap<Iri, Cycle>,
+    max_depth: usize,
+) -> Result<CauseFrame, String> {
+    let Some(root) = index.get(anchor) else {
+        return Err(format!("{}: события нет в контекстуальном индексе", anchor));
+    };
+    let max_depth = max_depth.min(MAX_DEPTH);
+    let mut steps: Vec<CauseStep> = Vec::new();
+    let mut seen: BTreeSet<Iri> = BTreeSet::from([anchor.clone()]);
+    let mut queue = VecDeque::from([(anchor.clone(), 0)]);
+    let mut truncated = false;
+    while let Some((event, depth)) = queue.pop_front() {
+        for (cause, relation) in predecessors(&event, index) {
+            if seen.contains(&cause) {
+                continue;
+            }
+            if depth == max_depth {
+                truncated = true;
+                break;
+            }
+            seen.insert(cause.clone());
+            let indexed = index.get(&cause);
+            steps.push(CauseStep {
+                event: cause.clone(),
+                label: indexed.and_then(|e| e.label.clone()),
+                occurred_at: indexed.and_then(|e| e.occurred_at.clone()),
+                perturbations: indexed.map(|e| e.actions.iter().cloned().collect()).unwrap_or_default(),
+                relation,
+                caused: event.clone(),
+                depth: depth + 1,
+            });
+            queue.push_back((cause, depth + 1));
+        }
+    }
+    // Хронология: события без даты — по удалённости от исходного
+    steps.sort_by(|a, b| {
+        (a.occurred_at.is_none(), &a.occurred_at, b.depth, &a.event).cmp(&(
+            b.occurred_at.is_none(),
+            &b.occurred_at,
+            a.depth,
+            &b.event,
+        ))
+    });
+
+    // Циклы и предпосылки — в том же порядке: от ранних событий к исходному
+    let mut cycles: BTreeSet<Iri> = root.cycles.clone();
+    let mut preconditions_found: Vec<Precondition> = Vec::new();
+    for event in steps.iter().map(|s| &s.event).chain([anchor]) {
+        cycles.extend(index.get(event).into_iter().flat_map(|e| e.cycles.iter().cloned()));
+        for p in preconditions(store, event)? {
+            if !preconditions_found.iter().any(|q| q.text == p.text) {
+                preconditions_found.push(p);
+            }
+        }
+    }
+
+    let title = |label: &Option<String>, event: &Iri| label.clone().unwrap_or_else(|| event.to_string());
+    let cycles: Vec<Cycle> = cycles.iter().filter_map(|id| known_cycles.get(id).cloned()).collect();
+    let mut explanation: Vec<String> = cycles
+        .iter()
+        .map(|c| {
+            let name = named(c.label.clone(), &c.id);
+            match c.period {
+                Some(period) => format!("Цикл: {}, период ≈ {:.1} г.", name, period),
+                None => format!("Цикл: {}", name),
+            }
+        })
+        .collect();
+    explanation.extend(preconditions_found.iter().map(|p| format!("Предпосылка: {}", p.text)));
+    for step in &steps {
+        let verb = if step.relation.curie() == "temporal:triggeredBy" { "стало триггером для" } else { "привело к" };
+        let caused = index.get(&step.caused).and_then(|e| e.label.clone());
+        explanation.push(format!(
+            "{} — {} {}: {}",
+            step.occurred_at.as_deref().unwrap_or("дата неизвестна"),
+            title(&step.label, &step.event),
+            verb,
+            title(&caused, &step.caused)
+        ));
+    }
+    explanation.push(format!(
+        "{} — {}",
+        root.occurred_at.as_deref().unwrap_or("дата неизвестна"),
+        title(&root.label, anchor)
+    ));
+
+    Ok(CauseFrame {
+        anchor: anchor.clone(),
+        via_analog,
+        steps,
+        cycles,
+        preconditions: preconditions_found,
+        explanation,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{self, cycles};
+    use crate::knowledge::packs;
+    use oxigraph::store::Store;
+
+    /// E0 → {E1, E2}, E1 → E3 → E0 (цикл следствий замыкается на исходное событие)
+    const CHAIN: &str = r#"
+        @prefix fus: <http://onto16.org/fus/> .
+        @prefix pert: <http://onto16.org/pert/> .
+        @prefix temporal: <http://onto16.org/temporal/> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        fus:Inv_Energy a fus:Invariant ; fus:belongsToLevel fus:PhysicalLevel .
+        fus:Inv_Agency a fus:Invariant ; fus:belongsToLevel fus:PsychicLevel .
+        pert:Shock pert:affectsInvariant fus:Inv_Energy .
+        pert:Doubt pert:affectsInvariant fus:Inv_Agency .
+
+        temporal:E0 a temporal:Event ; temporal:occurredAt "2000-01-01"^^xsd:date ;
+            temporal:ledTo temporal:E2, temporal:E1 .
+        temporal:E1 a temporal:Event ; temporal:occurredAt "2001-01-01"^^xsd:date ;
+            temporal:describesAction pert:Shock ; temporal:ledTo temporal:E3 .
+        temporal:E2 a temporal:Event ; temporal:occurredAt "2002-01-01"^^xsd:date ;
+            temporal:describesAction pert:Doubt .
+        temporal:E3 a temporal:Event ; temporal:occurredAt "2003-01-01"^^xsd:date ;
+            temporal:describesAction pert:Doubt ; temporal:ledTo temporal:E0 .
+    "#;
+
+    fn setup() -> (BTreeMap<Iri, IndexedEvent>, InvariantRegistry) {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/frames.ttl", CHAIN.as_bytes()).unwrap();
+        (context::index(&store).unwrap(), InvariantRegistry::load(&store).unwrap())
+    }
+
+    fn names(nodes: &[FrameNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.event.local_name()).collect()
+    }
+
+    #[test]
+    fn effect_frame_follows_led_to_and_projects_levels() {
+        let (index, invariants) = setup();
+        let anchor = Iri::from_curie("temporal:E0");
+        let frame = effect_frame(&anchor, false, &index, &invariants, DEFAULT_DEPTH).unwrap();
+
+        // Следствия по дате; E3 → E0 не возвращается к исходному событию
+        assert_eq!(names(&frame.root.children), ["E1", "E2"]);
+        let e1 = &frame.root.children[0];
+        assert_eq!(e1.levels, [FusLevel::Physical]);
+        assert_eq!(names(&e1.children), ["E3"]);
+        assert!(e1.children[0].children.is_empty());
+        assert_eq!(e1.children[0].depth, 2);
+        assert!(!frame.truncated);
+
+        // Вес: E1 и E2 — по 1, E3 — 1/2; психический уровень 1.5 из 2.5
+        let levels: Vec<(FusLevel, f32)> = frame.projection.iter().map(|p| (p.level, p.likelihood)).collect();
+        assert_eq!(levels, [(FusLevel::Psychic, 0.6), (FusLevel::Physical, 0.4)]);
+        assert_eq!(frame.projection[0].invariants, [Iri::from_curie("fus:Inv_Agency")]);
+    }
+
+    #[test]
+    fn effect_frame_stops_at_max_depth() {
+        let (index, invariants) = setup();
+        let frame = effect_frame(&Iri::from_curie("temporal:E0"), true, &index, &invariants, 1).unwrap();
+        assert!(frame.via_analog);
+        assert!(frame.truncated);
+        assert!(frame.root.children.iter().all(|n| n.children.is_empty()));
+        assert!(effect_frame(&Iri::from_curie("temporal:Missing"), false, &index, &invariants, 1).is_err());
+    }
+
+    /// T (триггер без даты) и C1 → C2 → A; C0 → C2 — вторым путём к уже найденному C2
+    const CAUSES: &str = r#"
+        @prefix fus: <http://onto16.org/fus/> .
+        @prefix pert: <http://onto16.org/pert/> .
+        @prefix temporal: <http://onto16.org/temporal/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        pert:Leak rdfs:label "Утечка" ; pert:affectsInvariant fus:Inv_Energy .
+        fus:Inv_Energy rdfs:label "Энергия" .
+
+        temporal:A a temporal:Event ; rdfs:label "Событие" ; temporal:occurredAt "2010-01-01"^^xsd:date ;
+            temporal:triggeredBy temporal:T ; temporal:describesAction pert:Leak .
+        temporal:T a temporal:Event ; rdfs:label "Триггер" .
+        temporal:C2 a temporal:Event ; temporal:occurredAt "2005-01-01"^^xsd:date ; temporal:ledTo temporal:A .
+        temporal:C1 a temporal:Event ; temporal:occurredAt "2001-01-01"^^xsd:date ; temporal:ledTo temporal:C2 .
+        temporal:C0 a temporal:Event ; temporal:occurredAt "2003-01-01"^^xsd:date ; temporal:ledTo temporal:C2 .
+    "#;
+
+    #[test]
+    fn cause_frame_is_chronological() {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/causes.ttl", CAUSES.as_bytes()).unwrap();
+        let index = context::index(&store).unwrap();
+        let anchor = Iri::from_curie("temporal:A");
+        let known_cycles = cycles::load(&store, &index).unwrap();
+        let frame = cause_frame(&store, &anchor, false, &index, &known_cycles, DEFAULT_DEPTH).unwrap();
+
+        // От раннего к позднему; событие без даты — в конце
+        let steps: Vec<(&str, &str, usize)> = frame
+            .steps
+            .iter()
+            .map(|s| (s.event.local_name(), s.relation.local_name(), s.depth))
+            .collect();
+        assert_eq!(
+            steps,
+            [("C1", "ledTo", 2), ("C0", "ledTo", 2), ("C2", "ledTo", 1), ("T", "triggeredBy", 1)]
+        );
+        assert!(!frame.truncated);
+        assert_eq!(
+            frame.explanation,
+            [
+                "Предпосылка: Возмущение Утечка (pert:Leak) затрагивает Энергия (fus:Inv_Energy)",
+                "2001-01-01 — temporal:C1 привело к: temporal:C2",
+                "2003-01-01 — temporal:C0 привело к: temporal:C2",
+                "2005-01-01 — temporal:C2 привело к: Событие",
+                "дата неизвестна — Триггер стало триггером для: Событие",
+                "2010-01-01 — Событие",
+            ]
+        );
+
+        let frame = cause_frame(&store, &anchor, false, &index, &known_cycles, 1).unwrap();
+        assert!(frame.truncated);
+        assert_eq!(frame.steps.len(), 2);
+    }
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Контекстуальный анализ: событие новости в истории контекстуального индекса
+// (`kb/dynamics/context-index.ttl` и события, добавленные во время работы)
+
+pub mod analogs;
+pub mod cycles;
+pub mod frames;
+pub mod ontogenesis;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use oxigraph::model::Term;
+use oxigraph::store::Store;
+use serde::Serialize;
+
+use crate::iri::Iri;
+use crate::sparql;
+
+/// Событие индекса со структурными признаками
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct IndexedEvent {
+    pub id: Option<Iri>, // None — новое событие, которого нет в индексе
+    pub label: Option<String>,
+    pub occurred_at: Option<String>,
+    pub actions: BTreeSet<Iri>, // типы возмущений: триггер приведён к типу по pert:leadsTo
+    pub actors: BTreeSet<Iri>,
+    pub cycles: BTreeSet<Iri>,       // temporal:partOfCycle: циклы temporal:Cycle
+    pub analogous_to: BTreeSet<Iri>, // явные связи temporal:analogousTo (в обе стороны)
+    pub led_to: BTreeSet<Iri>,       // temporal:ledTo: прямые следствия
+    pub triggered_by: BTreeSet<Iri>, // temporal:triggeredBy: непосредственные триггеры
+    pub invariants: BTreeSet<Iri>,   // затронутые инварианты: по типам возмущений и rule:flagsInvariant
+}
+
+const EVENTS_QUERY: &str = r#"
+SELECT ?event (SAMPLE(?l) AS ?label) (SAMPLE(?d) AS ?date) WHERE {
+    ?event a temporal:Event .
+    OPTIONAL { ?event rdfs:label ?l }
+    OPTIONAL { ?event temporal:occurredAt ?d }
+}
+GROUP BY ?event
+"#;
+
+const ACTIONS_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    ?event a temporal:Event ;
+        temporal:describesAction ?action .
+    OPTIONAL { ?action a pert:Trigger ; pert:leadsTo ?led }
+    BIND(COALESCE(?led, ?action) AS ?value)
+}
+"#;
+
+const ACTORS_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    ?event a temporal:Event ;
+        temporal:involvedActor ?value .
+}
+"#;
+
+const CYCLES_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    ?event a temporal:Event ;
+        temporal:partOfCycle ?value .
+}
+"#;
+
+const ANALOGIES_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    { ?event temporal:analogousTo ?value } UNION { ?value temporal:analogousTo ?event }
+    ?event a temporal:Event .
+}
+"#;
+
+const LED_TO_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    ?event a temporal:Event ;
+        temporal:ledTo ?value .
+}
+"#;
+
+const TRIGGERED_BY_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    ?event a temporal:Event ;
+        temporal:triggeredBy ?value .
+}
+"#;
+
+const INVARIANTS_QUERY: &str = r#"
+SELECT DISTINCT ?event ?value WHERE {
+    ?event a temporal:Event .
+    {
+        ?event temporal:describesAction ?action .
+        OPTIONAL { ?action a pert:Trigger ; pert:leadsTo ?led }
+        BIND(COALESCE(?led, ?action) AS ?pert)
+        ?pert pert:affectsInvariant ?value .
+    } UNION {
+        ?event rule:flagsInvariant ?value .
+    }
+}
+"#;
+
+/// Пары (событие, значение) запроса признака
+fn features(store: &Store, query: &str) -> Result<Vec<(Iri, Term)>, String> {
+    Ok(sparql::select(store, query, &[])?
+        .into_iter()
+        .filter_map(|row| Some((sparql::iri(&row, "event")?, row.get("value")?.clone())))
+        .collect())
+}
+
+/// Все события индекса по IRI
+pub fn index(store: &Store) -> Result<BTreeMap<Iri, IndexedEvent>, String> {
+    let mut events = BTreeMap::new();
+    for row in sparql::select(store, EVENTS_QUERY, &[])? {
+        let Some(id) = sparql::iri(&row, "event") else {
+            continue;
+        };
+        events.insert(
+            id.clone(),
+            IndexedEvent {
+                id: Some(id),
+                label: sparql::literal(&row, "label"),
+                occurred_at: sparql::literal(&row, "date"),
+                ..Default::default()
+            },
+        );
+    }
+
+    let as_iri = |term: Term| match term {
+        Term::NamedNode(node) => Some(Iri::from(node)),
+        _ => None,
+    };
+    for (event, value) in features(store, ACTIONS_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.actions.insert(value);
+        }
+    }
+    for (event, value) in features(store, ACTORS_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.actors.insert(value);
+        }
+    }
+    for (event, value) in features(store, CYCLES_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.cycles.insert(value);
+        }
+    }
+    for (event, value) in features(store, ANALOGIES_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.analogous_to.insert(value);
+        }
+    }
+    for (event, value) in features(store, LED_TO_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.led_to.insert(value);
+        }
+    }
+    for (event, value) in features(store, TRIGGERED_BY_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.triggered_by.insert(value);
+        }
+    }
+    for (event, value) in features(store, INVARIANTS_QUERY)? {
+        if let (Some(e), Some(value)) = (events.get_mut(&event), as_iri(value)) {
+            e.invariants.insert(value);
+        }
+    }
+    Ok(events)
+}
@@ -0,0 +1,427 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Фазы онтогенеза по темам и акторам (`kb/dynamics/ontogenesis.ttl`): конечный автомат
+// из фаз `onto:Phase` и переходов `onto:Transition`. Переход срабатывает, когда событие
+// описывает действие из его `onto:triggeredBy` — сам ресурс или экземпляр класса.
+// Тема — актор (`temporal:involvedActor`) или цикл (`temporal:partOfCycle`) событий индекса.
+// Где выход из фазы зависит от реакции, переход отмечен `onto:preferredForTemperament`:
+// по темпераменту читателя прогнозируется вероятная ветвь, регрессивная — с предупреждением.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+
+use crate::context::IndexedEvent;
+use crate::core::astro::Temperament;
+use crate::iri::Iri;
+use crate::sparql;
+use crate::trace::Trace;
+
+const PHASES_QUERY: &str = r#"
+SELECT ?phase (SAMPLE(?l) AS ?label) (SAMPLE(?i) AS ?initial) (SAMPLE(?r) AS ?regressive) WHERE {
+    ?phase a onto:Phase .
+    OPTIONAL { ?phase rdfs:label ?l }
+    OPTIONAL { ?phase a onto:InitialPhase . BIND(true AS ?i) }
+    OPTIONAL { ?phase a onto:RegressivePhase . BIND(true AS ?r) }
+}
+GROUP BY ?phase
+ORDER BY ?phase
+"#;
+
+const TRANSITIONS_QUERY: &str = r#"
+SELECT DISTINCT ?transition ?from ?to ?match WHERE {
+    ?transition a onto:Transition ;
+        onto:from ?from ;
+        onto:to ?to .
+    { ?transition onto:triggeredBy ?match }
+    UNION
+    { ?transition onto:triggeredBy ?trigger . ?match a ?trigger }
+}
+ORDER BY ?transition
+"#;
+
+const PREFERENCES_QUERY: &str = r#"
+SELECT DISTINCT ?transition ?temperament WHERE {
+    ?transition a onto:Transition ;
+        onto:preferredForTemperament ?temperament .
+}
+"#;
+
+const TEMPERAMENTS_QUERY: &str = r#"
+SELECT ?temperament (SAMPLE(?l) AS ?label) WHERE {
+    ?temperament a fus:Temperament .
+    OPTIONAL { ?temperament rdfs:label ?l }
+}
+GROUP BY ?temperament
+"#;
+
+const LABEL_QUERY: &str = r#"
+SELECT ?subject (SAMPLE(?l) AS ?label) WHERE {
+    OPTIONAL { ?subject rdfs:label ?l }
+}
+GROUP BY ?subject
+"#;
+
+/// Переход автомата
+#[derive(Debug, Clone)]
+struct Transition {
+    id: Iri,
+    from: Iri,
+    to: Iri,
+    matches: BTreeSet<Iri>,   // триггеры и их экземпляры
+    preferred: BTreeSet<Iri>, // темпераменты onto:preferredForTemperament
+}
+
+/// Автомат фаз онтогенеза из KB
+#[derive(Debug, Clone)]
+pub struct PhaseMachine {
+    labels: BTreeMap<Iri, Option<String>>, // фазы и темпераменты
+    initial: Iri,
+    regressive: BTreeSet<Iri>,
+    transitions: Vec<Transition>, // по IRI: из нескольких подходящих срабатывает первый
+}
+
+/// Переход по событию
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PhaseStep {
+    pub event: Option<Iri>, // None — событие анализа, которого нет в индексе
+    pub label: Option<String>,
+    pub occurred_at: Option<String>,
+    pub transition: Iri,
+    pub from: Iri,
+    pub to: Iri,
+    pub action: Iri, // действие события, запустившее переход
+    pub visit: usize, // в который раз тема входит в фазу `to`
+}
+
+/// Фаза темы и история переходов
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PhaseTrack {
+    pub subject: Iri,
+    pub subject_label: Option<String>,
+    pub phase: Iri,
+    pub phase_label: Option<String>,
+    pub visit: usize, // в который раз тема в текущей фазе; 0 — начальная фаза, в которую не возвращались
+    pub history: Vec<PhaseStep>,
+}
+
+/// Вероятный выход из фазы для темперамента читателя
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransitionOutlook {
+    pub from: Iri,
+    pub from_label: Option<String>,
+    pub temperament: Iri, // temper:Phlegmatic…
+    pub temperament_label: Option<String>,
+    pub transition: Option<Iri>, // None — пробел: для темперамента KB не задаёт переход
+    pub to: Option<Iri>,
+    pub to_label: Option<String>,
+    pub regressive: bool, // вероятная ветвь ведёт в onto:RegressivePhase
+}
+
+impl PhaseMachine {
+    /// Фазы и переходы из KB; начальная фаза (`onto:InitialPhase`) должна быть одна
+    pub fn load(store: &Store) -> Result<Self, String> {
+        let mut labels = BTreeMap::new();
+        let mut initial = Vec::new();
+        let mut regressive = BTreeSet::new();
+        for row in sparql::select(store, PHASES_QUERY, &[])? {
+            if let Some(phase) = sparql::iri(&row, "phase") {
+                if row.get("initial").is_some() {
+                    initial.push(phase.clone());
+                }
+                if row.get("regressive").is_some() {
+                    regressive.insert(phase.clone());
+                }
+                labels.insert(phase, sparql::literal(&row, "label"));
+            }
+        }
+
+        let mut transitions: Vec<Transition> = Vec::new();
+        for row in sparql::select(store, TRANSITIONS_QUERY, &[])? {
+            let (Some(id), Some(from), Some(to), Some(matched)) = (
+                sparql::iri(&row, "transition"),
+                sparql::iri(&row, "from"),
+                sparql::iri(&row, "to"),
+                sparql::iri(&row, "match"),
+            ) else {
+                continue;
+            };
+            match transitions.iter_mut().find(|t| t.id == id) {
+                Some(t) => {
+                    t.matches.insert(matched);
+                }
+                None => transitions.push(Transition {
+                    id,
+                    from,
+                    to,
+                    matches: BTreeSet::from([matched]),
+                    preferred: BTreeSet::new(),
+                }),
+            }
+        }
+        for row in sparql::select(store, PREFERENCES_QUERY, &[])? {
+            let (Some(id), Some(temperament)) = (sparql::iri(&row, "transition"), sparql::iri(&row, "temperament")) else {
+                continue;
+            };
+            if let Some(t) = transitions.iter_mut().find(|t| t.id == id) {
+                t.preferred.insert(temperament);
+            }
+        }
+        for row in sparql::select(store, TEMPERAMENTS_QUERY, &[])? {
+            if let Some(temperament) = sparql::iri(&row, "temperament") {
+                labels.insert(temperament, sparql::literal(&row, "label"));
+            }
+        }
+
+        let [initial] = initial.as_slice() else {
+            let initial: Vec<String> = initial.iter().map(Iri::curie).collect();
+            return Err(format!(
+                "Онтогенез: ожидалась одна фаза onto:InitialPhase, найдено: [{}]",
+                initial.join(", ")
+            ));
+        };
+        Ok(Self {
+            initial: initial.clone(),
+            labels,
+            regressive,
+            transitions,
+        })
+    }
+
+    /// Начальная фаза
+    pub fn initial(&self) -> &Iri {
+        &self.initial
+    }
+
+    /// Метка фазы или темперамента
+    pub fn label(&self, id: &Iri) -> Option<String> {
+        self.labels.get(id).cloned().flatten()
+    }
+
+    /// Переход из фазы по действиям события и запустившее его действие
+    fn next(&self, phase: &Iri, actions: &BTreeSet<Iri>) -> Option<(&Transition, Iri)> {
+        self.transitions
+            .iter()
+            .filter(|t| &t.from == phase)
+            .find_map(|t| Some((t, actions.iter().find(|a| t.matches.contains(a))?.clone())))
+    }
+
+    /// Фаза, в которую событие с такими действиями переводит тему из `phase`
+    pub fn target(&self, phase: &Iri, actions: &BTreeSet<Iri>) -> Option<&Iri> {
+        self.next(phase, actions).map(|(t, _)| &t.to)
+    }
+
+    /// Вероятный выход из фазы для темперамента; `None` — выход из фазы
+    /// не зависит от темперамента (ни у одного перехода нет предпочтений)
+    pub fn outlook(&self, phase: &Iri, temperament: Temperament) -> Option<TransitionOutlook> {
+        let mut guided = self
+            .transitions
+            .iter()
+            .filter(|t| &t.from == phase && !t.preferred.is_empty())
+            .peekable();
+        guided.peek()?;
+        let uri = temperament.uri();
+        let preferred = guided.find(|t| t.preferred.contains(&uri));
+        Some(TransitionOutlook {
+            from: phase.clone(),
+            from_label: self.label(phase),
+            temperament_label: self.label(&uri),
+            temperament: uri,
+            transition: preferred.map(|t| t.id.clone()),
+            to: preferred.map(|t| t.to.clone()),
+            to_label: preferred.and_then(|t| self.label(&t.to)),
+            regressive: preferred.is_some_and(|t| self.regressive.contains(&t.to)),
+        })
+    }
+
+    /// Пустая история темы: начальная фаза
+    pub fn start(&self, store: &Store, subject: &Iri) -> Result<PhaseTrack, String> {
+        let subject_label = sparql::select(store, LABEL_QUERY, &[("subject", subject.term())])?
+            .first()
+            .and_then(|row| sparql::literal(row, "label"));
+        Ok(PhaseTrack {
+            subject: subject.clone(),
+            subject_label,
+            phase: self.initial.clone(),
+            phase_label: self.label(&self.initial),
+            visit: 0,
+            history: Vec::new(),
+        })
+    }
+
+    /// Применяет событие к теме; `None` — ни один переход из текущей фазы не сработал
+    pub fn apply<'t>(&self, track: &'t mut PhaseTrack, event: &IndexedEvent) -> Option<&'t PhaseStep> {
+        let (transition, action) = self.next(&track.phase, &event.actions)?;
+        let visit = 1 + track.history.iter().filter(|s| s.to == transition.to).count();
+        track.history.push(PhaseStep {
+            event: event.id.clone(),
+            label: event.label.clone(),
+            occurred_at: event.occurred_at.clone(),
+            transition: transition.id.clone(),
+            from: transition.from.clone(),
+            to: transition.to.clone(),
+            action,
+            visit,
+        });
+        track.phase = transition.to.clone();
+        track.phase_label = self.label(&transition.to);
+        track.visit = visit;
+        track.history.last()
+    }
+}
+
+/// События индекса по теме в хронологическом порядке, не позже `until`, без `exclude`
+fn events_about<'i>(
+    index: &'i BTreeMap<Iri, IndexedEvent>,
+    subject: &Iri,
+    until: Option<&str>,
+    exclude: Option<&Iri>,
+) -> Vec<&'i IndexedEvent> {
+    let mut events: Vec<&IndexedEvent> = index
+        .values()
+        .filter(|e| e.actors.contains(subject) || e.cycles.contains(subject))
+        .filter(|e| e.id.as_ref() != exclude)
+        .filter(|e| match (until, e.occurred_at.as_deref()) {
+            (Some(until), Some(date)) => date <= until,
+            _ => true,
+        })
+        .collect();
+    events.sort_by(|a, b| {
+        (a.occurred_at.is_none(), &a.occurred_at, &a.id).cmp(&(b.occurred_at.is_none(), &b.occurred_at, &b.id))
+    });
+    events
+}
+
+/// Фаза темы по событиям индекса; `until` и `exclude` — история до события анализа
+pub fn track(
+    store: &Store,
+    machine: &PhaseMachine,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    subject: &Iri,
+    until: Option<&str>,
+    exclude: Option<&Iri>,
+) -> Result<PhaseTrack, String> {
+    let mut track = machine.start(store, subject)?;
+    for event in events_about(index, subject, until, exclude) {
+        machine.apply(&mut track, event);
+    }
+    Ok(track)
+}
+
+/// Фазы тем события анализа: история по индексу, затем само событие
+pub fn locate(
+    store: &Store,
+    machine: &PhaseMachine,
+    index: &BTreeMap<Iri, IndexedEvent>,
+    event: &IndexedEvent,
+    subjects: &BTreeSet<Iri>,
+    mut trace: Option<&mut Trace>,
+) -> Result<Vec<PhaseTrack>, String> {
+    if subjects.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut tracks = Vec::new();
+    for subject in subjects {
+        let mut track = track(store, machine, index, subject, event.occurred_at.as_deref(), event.id.as_ref())?;
+        let step = machine.apply(&mut track, event).cloned();
+        if let Some(trace) = trace.as_deref_mut() {
+            match step {
+                Some(step) => {
+                    trace.triple(&step.transition, "onto:triggeredBy", &step.action);
+                    trace.scoring_step(format!(
+                        "Фаза {}: {} → {} ({}-й раз) по {}",
+                        subject, step.from, step.to, step.visit, step.action
+                    ));
+                }
+                None => trace.scoring_step(format!("Фаза {}: {} без изменений", subject, track.phase)),
+            }
+        }
+        tracks.push(track);
+    }
+    Ok(tracks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context;
+    use crate::knowledge::packs;
+
+    /// Две фазы: в кризис ведёт любое возмущение (экземпляр класса `pert:Disturbance`),
+    /// обратно — ровно действие `pert:Repair`
+    const MACHINE: &str = r#"
+        @prefix onto: <http://onto16.org/ontogeny/> .
+        @prefix pert: <http://onto16.org/pert/> .
+        @prefix temporal: <http://onto16.org/temporal/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        onto:Stable a onto:Phase, onto:InitialPhase ; rdfs:label "Стабильность" .
+        onto:Crisis a onto:Phase ; rdfs:label "Кризис" .
+        onto:ToCrisis a onto:Transition ; onto:from onto:Stable ; onto:to onto:Crisis ; onto:triggeredBy pert:Disturbance .
+        onto:ToStable a onto:Transition ; onto:from onto:Crisis ; onto:to onto:Stable ; onto:triggeredBy pert:Repair .
+
+        pert:Leak a pert:Disturbance .
+
+        temporal:E1 a temporal:Event ; temporal:occurredAt "2000-01-01"^^xsd:date ;
+            temporal:involvedActor temporal:Actor ; temporal:describesAction pert:Leak .
+        temporal:E2 a temporal:Event ; temporal:occurredAt "2001-01-01"^^xsd:date ;
+            temporal:involvedActor temporal:Actor ; temporal:describesAction pert:Repair .
+        temporal:E3 a temporal:Event ; temporal:occurredAt "2002-01-01"^^xsd:date ;
+            temporal:involvedActor temporal:Actor ; temporal:describesAction pert:Repair .
+        temporal:E4 a temporal:Event ; temporal:occurredAt "2003-01-01"^^xsd:date ;
+            temporal:involvedActor temporal:Actor ; temporal:describesAction pert:Leak .
+    "#;
+
+    fn setup() -> (Store, PhaseMachine, BTreeMap<Iri, IndexedEvent>) {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/ontogenesis.ttl", MACHINE.as_bytes()).unwrap();
+        let machine = PhaseMachine::load(&store).unwrap();
+        let index = context::index(&store).unwrap();
+        (store, machine, index)
+    }
+
+    #[test]
+    fn second_crisis_is_counted() {
+        let (store, machine, index) = setup();
+        let track = track(&store, &machine, &index, &Iri::from_curie("temporal:Actor"), None, None).unwrap();
+
+        // E3 (pert:Repair в стабильной фазе) перехода не даёт
+        let steps: Vec<(&str, &str, usize)> = track
+            .history
+            .iter()
+            .map(|s| (s.event.as_ref().unwrap().local_name(), s.to.local_name(), s.visit))
+            .collect();
+        assert_eq!(steps, [("E1", "Crisis", 1), ("E2", "Stable", 1), ("E4", "Crisis", 2)]);
+        assert_eq!(track.phase, Iri::from_curie("onto:Crisis"));
+        assert_eq!(track.phase_label.as_deref(), Some("Кризис"));
+        assert_eq!(track.visit, 2);
+    }
+
+    #[test]
+    fn history_stops_before_the_analysed_event() {
+        let (store, machine, index) = setup();
+        let actor = Iri::from_curie("temporal:Actor");
+        let e4 = Iri::from_curie("temporal:E4");
+        let track = track(&store, &machine, &index, &actor, Some("2003-01-01"), Some(&e4)).unwrap();
+        assert_eq!(track.phase, Iri::from_curie("onto:Stable"));
+        assert_eq!(track.visit, 1);
+    }
+
+    #[test]
+    fn trigger_matches_instance_and_resource() {
+        let (_, machine, _) = setup();
+        let stable = Iri::from_curie("onto:Stable");
+        let crisis = Iri::from_curie("onto:Crisis");
+        let actions = |curie: &str| BTreeSet::from([Iri::from_curie(curie)]);
+
+        // Класс-триггер срабатывает и на экземпляр, и на сам класс
+        assert_eq!(machine.target(&stable, &actions("pert:Leak")), Some(&crisis));
+        assert_eq!(machine.target(&stable, &actions("pert:Disturbance")), Some(&crisis));
+        // Триггер-ресурс срабатывает на само действие
+        assert_eq!(machine.target(&crisis, &actions("pert:Repair")), Some(&stable));
+        assert_eq!(machine.target(&stable, &actions("pert:Repair")), None);
+    }
+}
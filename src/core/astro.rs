@@ -0,0 +1,656 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Астропсихологический профиль по дате рождения:
+// западный знак (элемент, качество), китайское животное, темперамент
+
+use oxigraph::store::Store;
+use serde::Serialize;
+
+use crate::iri::Iri;
+use crate::sparql;
+use crate::trace::Trace;
+
+// === Справочники ===
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Element {
+    Fire,
+    Earth,
+    Air,
+    Water,
+    Wood,
+    Metal,
+}
+
+pub const ELEMENTS: [Element; 6] = [
+    Element::Fire,
+    Element::Earth,
+    Element::Air,
+    Element::Water,
+    Element::Wood,
+    Element::Metal,
+];
+
+impl Element {
+    pub fn uri(&self) -> Iri {
+        Iri::from_curie(&format!("fus:{:?}", self))
+    }
+
+    /// Элемент по IRI ресурса KB (`fus:Fire`)
+    pub fn from_uri(uri: &Iri) -> Option<Self> {
+        ELEMENTS.into_iter().find(|e| e.uri() == *uri)
+    }
+}
+
+/// Качество (крест) западного знака
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Quality {
+    Cardinal,
+    Fixed,
+    Mutable,
+}
+
+pub const QUALITIES: [Quality; 3] = [Quality::Cardinal, Quality::Fixed, Quality::Mutable];
+
+impl Quality {
+    pub fn uri(&self) -> Iri {
+        Iri::from_curie(&format!("fus:{:?}", self))
+    }
+
+    /// Качество по IRI ресурса KB (`fus:Cardinal`)
+    pub fn from_uri(uri: &Iri) -> Option<Self> {
+        QUALITIES.into_iter().find(|q| q.uri() == *uri)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Temperament {
+    Choleric,
+    Sanguine,
+    Phlegmatic,
+    Melancholic,
+}
+
+pub const TEMPERAMENTS: [Temperament; 4] = [
+    Temperament::Choleric,
+    Temperament::Sanguine,
+    Temperament::Phlegmatic,
+    Temperament::Melancholic,
+];
+
+impl Temperament {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Temperament::Choleric => "choleric",
+            Temperament::Sanguine => "sanguine",
+            Temperament::Phlegmatic => "phlegmatic",
+            Temperament::Melancholic => "melancholic",
+        }
+    }
+
+    pub fn uri(&self) -> Iri {
+        Iri::from_curie(&format!("temper:{:?}", self))
+    }
+
+    /// Темперамент по IRI ресурса KB (`temper:Choleric`)
+    pub fn from_uri(uri: &Iri) -> Option<Self> {
+        TEMPERAMENTS.into_iter().find(|t| t.uri() == *uri)
+    }
+}
+
+// === Западный зодиак ===
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum WesternSign {
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+pub const WESTERN_SIGNS: [WesternSign; 12] = [
+    WesternSign::Aries,
+    WesternSign::Taurus,
+    WesternSign::Gemini,
+    WesternSign::Cancer,
+    WesternSign::Leo,
+    WesternSign::Virgo,
+    WesternSign::Libra,
+    WesternSign::Scorpio,
+    WesternSign::Sagittarius,
+    WesternSign::Capricorn,
+    WesternSign::Aquarius,
+    WesternSign::Pisces,
+];
+
+impl WesternSign {
+    pub fn uri(&self) -> Iri {
+        Iri::from_curie(&format!("zodiac:{:?}", self))
+    }
+
+    /// Знак по IRI ресурса KB (`zodiac:Aries`)
+    pub fn from_uri(uri: &Iri) -> Option<Self> {
+        WESTERN_SIGNS.into_iter().find(|sign| sign.uri() == *uri)
+    }
+}
+
+/// Западный знак, как он описан в KB: диапазон дат, элемент, качество
+#[derive(Debug, Clone)]
+pub struct WesternSignEntry {
+    pub sign: WesternSign,
+    pub starts_on: (u32, u32), // (месяц, день), включительно
+    pub ends_on: (u32, u32),   // (месяц, день), включительно
+    pub element: Element,
+    pub quality: Quality,
+}
+
+impl WesternSignEntry {
+    /// Попадает ли день года в диапазон знака (с переходом через Новый год)
+    pub fn contains(&self, month: u32, day: u32) -> bool {
+        let date = (month, day);
+        if self.starts_on <= self.ends_on {
+            self.starts_on <= date && date <= self.ends_on
+        } else {
+            date >= self.starts_on || date <= self.ends_on
+        }
+    }
+}
+
+const WESTERN_QUERY: &str = r#"
+SELECT ?sign ?start ?end ?element ?quality WHERE {
+    ?sign a fus:WesternSign ;
+        fus:startsOn ?start ;
+        fus:endsOn ?end ;
+        fus:element ?element ;
+        fus:quality ?quality .
+}
+"#;
+
+/// Все западные знаки из KB (`fus:startsOn`, `fus:endsOn`, `fus:element`, `fus:quality`)
+pub fn western_signs(store: &Store) -> Result<Vec<WesternSignEntry>, String> {
+    let mut entries = Vec::new();
+    for row in sparql::select(store, WESTERN_QUERY, &[])? {
+        let (Some(sign_iri), Some(element_iri), Some(quality_iri)) = (
+            sparql::iri(&row, "sign"),
+            sparql::iri(&row, "element"),
+            sparql::iri(&row, "quality"),
+        ) else {
+            return Err("Западный знак KB: знак, fus:element и fus:quality должны быть ресурсами".to_string());
+        };
+        let sign = WesternSign::from_uri(&sign_iri)
+            .ok_or_else(|| format!("Неизвестный западный знак в KB: {}", sign_iri))?;
+        let date = |var: &str| {
+            sparql::literal(&row, var)
+                .as_deref()
+                .and_then(parse_month_day)
+                .ok_or_else(|| format!("{}: некорректная дата {}, ожидалось --ММ-ДД", sign.uri(), var))
+        };
+        entries.push(WesternSignEntry {
+            sign,
+            starts_on: date("start")?,
+            ends_on: date("end")?,
+            element: Element::from_uri(&element_iri)
+                .ok_or_else(|| format!("{}: неизвестный элемент {}", sign.uri(), element_iri))?,
+            quality: Quality::from_uri(&quality_iri)
+                .ok_or_else(|| format!("{}: неизвестное качество {}", sign.uri(), quality_iri))?,
+        });
+    }
+    Ok(entries)
+}
+
+/// Западный знак для дня года по диапазонам дат из KB
+pub fn western_sign(store: &Store, month: u32, day: u32) -> Result<WesternSignEntry, String> {
+    let mut matching: Vec<WesternSignEntry> = western_signs(store)?
+        .into_iter()
+        .filter(|entry| entry.contains(month, day))
+        .collect();
+    match matching.len() {
+        1 => Ok(matching.remove(0)),
+        0 => Err(format!("{:02}-{:02}: ни один западный знак KB не покрывает дату", month, day)),
+        _ => Err(format!(
+            "{:02}-{:02}: диапазоны знаков KB пересекаются ({})",
+            month,
+            day,
+            matching.iter().map(|e| e.sign.uri().curie()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// `xsd:gMonthDay` (`--03-21`) → (месяц, день)
+fn parse_month_day(value: &str) -> Option<(u32, u32)> {
+    let (month, day) = value.strip_prefix("--")?.split_once('-')?;
+    let date = (month.parse().ok()?, day.get(..2)?.parse().ok()?);
+    ((1..=12).contains(&date.0) && date.1 >= 1 && date.1 <= days_in_month(2000, date.0)).then_some(date)
+}
+
+// === Китайский гороскоп ===
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum ChineseAnimal {
+    Rat,
+    Ox,
+    Tiger,
+    Rabbit,
+    Dragon,
+    Snake,
+    Horse,
+    Goat,
+    Monkey,
+    Rooster,
+    Dog,
+    Pig,
+}
+
+pub const ANIMALS: [ChineseAnimal; 12] = [
+    ChineseAnimal::Rat,
+    ChineseAnimal::Ox,
+    ChineseAnimal::Tiger,
+    ChineseAnimal::Rabbit,
+    ChineseAnimal::Dragon,
+    ChineseAnimal::Snake,
+    ChineseAnimal::Horse,
+    ChineseAnimal::Goat,
+    ChineseAnimal::Monkey,
+    ChineseAnimal::Rooster,
+    ChineseAnimal::Dog,
+    ChineseAnimal::Pig,
+];
+
+/// Первый год таблицы китайского Нового года
+const CNY_FIRST_YEAR: i32 = 1900;
+
+/// Дата китайского Нового года (месяц, день) для 1900–2100:
+/// второе новолуние после зимнего солнцестояния, по времени UTC+8
+const CHINESE_NEW_YEAR: [(u32, u32); 201] = [
+    (1, 31), (2, 19), (2,  8), (1, 29), (2, 16), (2,  4), (1, 25), (2, 13), (2,  2), (1, 22),
+    (2, 10), (1, 30), (2, 18), (2,  6), (1, 26), (2, 14), (2,  4), (1, 23), (2, 11), (2,  1),
+    (2, 20), (2,  8), (1, 28), (2, 16), (2,  5), (1, 24), (2, 13), (2,  2), (1, 23), (2, 10),
+    (1, 30), (2, 17), (2,  6), (1, 26), (2, 14), (2,  4), (1, 24), (2, 11), (1, 31), (2, 19),
+    (2,  8), (1, 27), (2, 15), (2,  5), (1, 25), (2, 13), (2,  2), (1, 22), (2, 10), (1, 29),
+    (2, 17), (2,  6), (1, 27), (2, 14), (2,  3), (1, 24), (2, 12), (1, 31), (2, 18), (2,  8),
+    (1, 28), (2, 15), (2,  5), (1, 25), (2, 13), (2,  2), (1, 21), (2,  9), (1, 30), (2, 17),
+    (2,  6), (1, 27), (2, 15), (2,  3), (1, 23), (2, 11), (1, 31), (2, 18), (2,  7), (1, 28),
+    (2, 16), (2,  5), (1, 25), (2, 13), (2,  2), (2, 20), (2,  9), (1, 29), (2, 17), (2,  6),
+    (1, 27), (2, 15), (2,  4), (1, 23), (2, 10), (1, 31), (2, 19), (2,  7), (1, 28), (2, 16),
+    (2,  5), (1, 24), (2, 12), (2,  1), (1, 22), (2,  9), (1, 29), (2, 18), (2,  7), (1, 26),
+    (2, 14), (2,  3), (1, 23), (2, 10), (1, 31), (2, 19), (2,  8), (1, 28), (2, 16), (2,  5),
+    (1, 25), (2, 12), (2,  1), (1, 22), (2, 10), (1, 29), (2, 17), (2,  6), (1, 26), (2, 13),
+    (2,  3), (1, 23), (2, 11), (1, 31), (2, 19), (2,  8), (1, 28), (2, 15), (2,  4), (1, 24),
+    (2, 12), (2,  1), (1, 22), (2, 10), (1, 30), (2, 17), (2,  6), (1, 26), (2, 14), (2,  2),
+    (1, 23), (2, 11), (2,  1), (2, 19), (2,  8), (1, 28), (2, 15), (2,  4), (1, 24), (2, 12),
+    (2,  2), (1, 21), (2,  9), (1, 29), (2, 17), (2,  5), (1, 26), (2, 14), (2,  3), (1, 23),
+    (2, 11), (1, 31), (2, 19), (2,  7), (1, 27), (2, 15), (2,  5), (1, 24), (2, 12), (2,  2),
+    (1, 22), (2,  9), (1, 29), (2, 17), (2,  6), (1, 26), (2, 14), (2,  3), (1, 24), (2, 10),
+    (1, 30), (2, 18), (2,  7), (1, 27), (2, 15), (2,  5), (1, 25), (2, 12), (2,  1), (1, 21),
+    (2,  9),
+];
+
+impl ChineseAnimal {
+    /// Животное года рождения с учётом границы китайского Нового года:
+    /// родившиеся до праздника относятся к предыдущему году.
+    /// Вне таблицы (1900–2100) граница неизвестна — животное по одному году
+    pub fn from_date(year: i32, month: u32, day: u32) -> Self {
+        let lunar_year = match usize::try_from(year - CNY_FIRST_YEAR)
+            .ok()
+            .and_then(|i| CHINESE_NEW_YEAR.get(i))
+        {
+            Some(new_year) if (month, day) < *new_year => year - 1,
+            _ => year,
+        };
+        Self::from_lunar_year(lunar_year)
+    }
+
+    /// Животное по одному году, без даты: граница Нового года не учитывается
+    pub fn from_lunar_year(year: i32) -> Self {
+        // 1900 — год Крысы
+        ANIMALS[(year - CNY_FIRST_YEAR).rem_euclid(12) as usize]
+    }
+
+    pub fn uri(&self) -> Iri {
+        Iri::from_curie(&format!("chinese:{:?}", self))
+    }
+}
+
+// === Профиль ===
+
+/// Интерпретационный профиль субъекта по дате рождения.
+/// Правило смешения (astro-profiles.ttl): китайский знак задаёт активность —
+/// `temperament`, западный знак уточняет этический фон — `ethical_background`.
+#[derive(Serialize, Debug, Clone)]
+pub struct AstroProfile {
+    pub western_sign: WesternSign,
+    pub element: Element,
+    pub quality: Quality,
+    pub chinese_animal: ChineseAnimal,
+    pub chinese_element: Element,
+    pub temperament: Temperament,
+    pub ethical_background: Temperament,
+}
+
+impl AstroProfile {
+    /// Западный знак, элементы и темпераменты берутся из KB; китайское животное —
+    /// по таблице Нового года
+    pub fn from_date(store: &Store, year: i32, month: u32, day: u32) -> Result<Self, String> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(format!("Некорректная дата рождения: {:04}-{:02}-{:02}", year, month, day));
+        }
+
+        let western = western_sign(store, month, day)?;
+        let chinese_animal = ChineseAnimal::from_date(year, month, day);
+        Ok(Self {
+            western_sign: western.sign,
+            element: western.element,
+            quality: western.quality,
+            chinese_animal,
+            chinese_element: kb_element(store, &chinese_animal.uri())?,
+            temperament: kb_temperament(store, &chinese_animal.uri())?,
+            ethical_background: kb_temperament(store, &western.sign.uri())?,
+        })
+    }
+}
+
+/// Профиль субъекта, под который адаптируется анализ
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Profile {
+    /// Нейтральный режим: профиль не задан или удалён, персонализации нет
+    #[default]
+    Neutral,
+    /// Только год рождения: животное года без учёта границы китайского Нового года
+    BirthYear(i32),
+    /// Полная дата рождения
+    BirthDate { year: i32, month: u32, day: u32 },
+}
+
+impl Profile {
+    /// Профиль по дате в формате `ГГГГ-ММ-ДД`
+    pub fn parse_date(date: &str) -> Result<Self, String> {
+        let bad = || format!("Ожидалась дата ГГГГ-ММ-ДД, получено: {}", date);
+        let parts: Vec<&str> = date.trim().split('-').collect();
+        let [y, m, d] = parts.as_slice() else {
+            return Err(bad());
+        };
+        let (year, month, day) = (
+            y.parse().map_err(|_| bad())?,
+            m.parse().map_err(|_| bad())?,
+            d.parse().map_err(|_| bad())?,
+        );
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(format!("Некорректная дата рождения: {:04}-{:02}-{:02}", year, month, day));
+        }
+        Ok(Profile::BirthDate { year, month, day })
+    }
+
+    /// Темперамент профиля по KB; `None` — нейтральный режим.
+    /// При переданном `trace` записывает шаги вывода и использованные триплеты.
+    pub fn temperament(&self, store: &Store, trace: Option<&mut Trace>) -> Result<Option<Temperament>, String> {
+        match *self {
+            Profile::Neutral => {
+                if let Some(trace) = trace {
+                    trace.temperament_step("Профиль не задан → нейтральный режим, темперамент не определяется");
+                }
+                Ok(None)
+            }
+            Profile::BirthYear(year) => {
+                let animal = ChineseAnimal::from_lunar_year(year);
+                let temperament = kb_temperament(store, &animal.uri())?;
+                if let Some(trace) = trace {
+                    trace.temperament_step(format!("Год рождения {} → {}", year, animal.uri()));
+                    trace.triple(animal.uri(), "fus:mapsToTemperament", temperament.uri());
+                    trace.temperament_step(format!("{} → {}", animal.uri(), temperament.as_str()));
+                }
+                Ok(Some(temperament))
+            }
+            Profile::BirthDate { year, month, day } => {
+                let profile = AstroProfile::from_date(store, year, month, day)?;
+                if let Some(trace) = trace {
+                    trace.temperament_step(format!(
+                        "Дата рождения {:04}-{:02}-{:02} → {} ({:?}, {:?}), {}",
+                        year,
+                        month,
+                        day,
+                        profile.western_sign.uri(),
+                        profile.element,
+                        profile.quality,
+                        profile.chinese_animal.uri()
+                    ));
+                    trace.triple(profile.chinese_animal.uri(), "fus:mapsToTemperament", profile.temperament.uri());
+                    trace.triple(
+                        profile.western_sign.uri(),
+                        "fus:mapsToTemperament",
+                        profile.ethical_background.uri(),
+                    );
+                    trace.temperament_step(format!(
+                        "Активность по китайскому знаку → {}; этический фон по западному → {}",
+                        profile.temperament.as_str(),
+                        profile.ethical_background.as_str()
+                    ));
+                }
+                Ok(Some(profile.temperament))
+            }
+        }
+    }
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// === Темперамент из KB ===
+
+const TEMPERAMENT_QUERY: &str = "SELECT ?sign ?temperament WHERE { ?sign fus:mapsToTemperament ?temperament }";
+
+/// Темперамент знака (`chinese:Tiger`, `zodiac:Aries`) по `fus:mapsToTemperament`.
+/// Нет связи, несколько разных связей или неизвестный темперамент — ошибка:
+/// astro-profiles.ttl — единственный источник истины, значения по умолчанию нет.
+pub fn kb_temperament(store: &Store, sign: &Iri) -> Result<Temperament, String> {
+    let rows = sparql::select(store, TEMPERAMENT_QUERY, &[("sign", sign.term())])?;
+    let mut found: Option<Temperament> = None;
+    for row in &rows {
+        let Some(iri) = sparql::iri(row, "temperament") else {
+            return Err(format!("{}: fus:mapsToTemperament указывает не на ресурс", sign));
+        };
+        let temperament = Temperament::from_uri(&iri)
+            .ok_or_else(|| format!("{}: неизвестный темперамент {}", sign, iri))?;
+        match found {
+            Some(previous) if previous != temperament => {
+                return Err(format!(
+                    "{}: противоречивые fus:mapsToTemperament — {} и {}",
+                    sign,
+                    previous.as_str(),
+                    temperament.as_str()
+                ))
+            }
+            _ => found = Some(temperament),
+        }
+    }
+    found.ok_or_else(|| format!("{}: в KB нет fus:mapsToTemperament", sign))
+}
+
+const SIGNS_QUERY: &str = r#"
+SELECT ?sign WHERE {
+    { ?sign a fus:ChineseSign } UNION { ?sign a fus:WesternSign }
+}
+"#;
+
+/// Сверка KB с ядром: каждое животное китайского цикла и каждый знак,
+/// объявленный в KB, должны однозначно отображаться в известный темперамент.
+/// Вызывается при загрузке KB, чтобы расхождение проявилось сразу, а не в анализе.
+pub fn verify_temperaments(store: &Store) -> Result<(), String> {
+    let mut signs: Vec<Iri> = ANIMALS.iter().map(|animal| animal.uri()).collect();
+    for row in sparql::select(store, SIGNS_QUERY, &[])? {
+        if let Some(sign) = sparql::iri(&row, "sign") {
+            if !signs.contains(&sign) {
+                signs.push(sign);
+            }
+        }
+    }
+
+    let errors: Vec<String> = signs
+        .iter()
+        .filter_map(|sign| kb_temperament(store, sign).err())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Темпераменты в KB не согласованы: {}", errors.join("; ")))
+    }
+}
+
+const ELEMENT_QUERY: &str = "SELECT ?sign ?element WHERE { ?sign fus:element ?element }";
+
+/// Элемент знака по `fus:element` в KB
+pub fn kb_element(store: &Store, sign: &Iri) -> Result<Element, String> {
+    let rows = sparql::select(store, ELEMENT_QUERY, &[("sign", sign.term())])?;
+    let iri = rows
+        .first()
+        .and_then(|row| sparql::iri(row, "element"))
+        .ok_or_else(|| format!("{}: в KB нет fus:element", sign))?;
+    Element::from_uri(&iri).ok_or_else(|| format!("{}: неизвестный элемент {}", sign, iri))
+}
+
+/// Сверка календаря KB: все 12 западных знаков описаны, и каждый день года,
+/// включая 29 февраля, попадает ровно в один знак
+pub fn verify_western_calendar(store: &Store) -> Result<(), String> {
+    let entries = western_signs(store)?;
+    let missing: Vec<String> = WESTERN_SIGNS
+        .iter()
+        .filter(|sign| !entries.iter().any(|e| e.sign == **sign))
+        .map(|sign| sign.uri().curie())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("В KB не описаны западные знаки: {}", missing.join(", ")));
+    }
+
+    for month in 1..=12 {
+        for day in 1..=days_in_month(2000, month) {
+            let count = entries.iter().filter(|e| e.contains(month, day)).count();
+            if count != 1 {
+                return Err(format!(
+                    "{:02}-{:02}: дату покрывают {} западных знаков KB вместо одного",
+                    month, day, count
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Все проверки астро-KB, выполняемые при загрузке
+pub fn verify(store: &Store) -> Result<(), String> {
+    verify_temperaments(store)?;
+    verify_western_calendar(store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::io::{RdfFormat, RdfParser};
+    use oxigraph::model::{GraphNameRef, NamedNodeRef, QuadRef};
+
+    fn kb() -> Store {
+        let store = Store::new().unwrap();
+        let parser = RdfParser::from_format(RdfFormat::Turtle)
+            .with_base_iri("http://onto16.org/kb/subjects/astro-profiles.ttl")
+            .unwrap();
+        store
+            .load_from_reader(parser, include_str!("../kb/subjects/astro-profiles.ttl").as_bytes())
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn chinese_new_year_boundary() {
+        // Китайский Новый год 1990 — 27 января
+        assert_eq!(ChineseAnimal::from_date(1990, 1, 26), ChineseAnimal::Snake);
+        assert_eq!(ChineseAnimal::from_date(1990, 1, 27), ChineseAnimal::Horse);
+        // Первый и последний годы таблицы
+        assert_eq!(ChineseAnimal::from_date(1900, 1, 30), ChineseAnimal::Pig);
+        assert_eq!(ChineseAnimal::from_date(1900, 1, 31), ChineseAnimal::Rat);
+        assert_eq!(ChineseAnimal::from_date(2100, 2, 8), ChineseAnimal::Goat);
+        assert_eq!(ChineseAnimal::from_date(2100, 2, 9), ChineseAnimal::Monkey);
+    }
+
+    #[test]
+    fn outside_the_table_the_year_alone_decides() {
+        assert_eq!(ChineseAnimal::from_date(1899, 1, 1), ChineseAnimal::Pig);
+        assert_eq!(ChineseAnimal::from_date(1899, 12, 31), ChineseAnimal::Pig);
+        assert_eq!(ChineseAnimal::from_date(2101, 12, 31), ChineseAnimal::Rooster);
+    }
+
+    #[test]
+    fn western_sign_boundaries() {
+        let kb = kb();
+        let sign = |month, day| western_sign(&kb, month, day).unwrap().sign;
+        assert_eq!(sign(2, 29), WesternSign::Pisces);
+        assert_eq!(sign(3, 20), WesternSign::Pisces);
+        assert_eq!(sign(3, 21), WesternSign::Aries);
+        assert_eq!(sign(12, 31), WesternSign::Capricorn);
+        assert_eq!(sign(1, 19), WesternSign::Capricorn);
+        assert_eq!(sign(1, 20), WesternSign::Aquarius);
+    }
+
+    #[test]
+    fn birth_date_profile() {
+        let kb = kb();
+        let profile = AstroProfile::from_date(&kb, 2000, 2, 29).unwrap();
+        assert_eq!(profile.western_sign, WesternSign::Pisces);
+        assert_eq!(profile.element, Element::Water);
+        assert_eq!(profile.chinese_animal, ChineseAnimal::Dragon);
+        assert!(AstroProfile::from_date(&kb, 2001, 2, 29).is_err());
+        // Год вне таблицы Нового года не мешает анализу
+        let profile = AstroProfile::from_date(&kb, 1899, 6, 1).unwrap();
+        assert_eq!(profile.chinese_animal, ChineseAnimal::Pig);
+        assert!(Profile::parse_date("2000-02-29").is_ok());
+        assert!(Profile::parse_date("29.02.2000").is_err());
+        let temperament = Profile::BirthDate { year: 1850, month: 3, day: 1 }.temperament(&kb, None).unwrap();
+        assert!(temperament.is_some());
+    }
+
+    #[test]
+    fn temperament_comes_from_the_kb() {
+        let kb = kb();
+        assert_eq!(kb_temperament(&kb, &Iri::from_curie("chinese:Tiger")), Ok(Temperament::Choleric));
+        assert_eq!(kb_temperament(&kb, &Iri::from_curie("zodiac:Cancer")), Ok(Temperament::Melancholic));
+        // Без связи в KB значения по умолчанию нет
+        assert!(kb_temperament(&kb, &Iri::from_curie("zodiac:Ophiuchus")).is_err());
+        assert_eq!(verify(&kb), Ok(()));
+
+        let conflicting = r#"
+            @prefix fus: <http://onto16.org/fus/> .
+            @prefix temper: <http://onto16.org/fus/temperament/> .
+            <http://onto16.org/fus/chinese/Tiger> fus:mapsToTemperament temper:Sanguine .
+        "#;
+        kb.load_from_reader(RdfFormat::Turtle, conflicting.as_bytes()).unwrap();
+        assert!(kb_temperament(&kb, &Iri::from_curie("chinese:Tiger")).is_err());
+        assert!(verify_temperaments(&kb).is_err());
+    }
+
+    #[test]
+    fn calendar_gaps_are_reported() {
+        let kb = kb();
+        assert_eq!(kb_element(&kb, &Iri::from_curie("chinese:Tiger")), Ok(Element::Wood));
+        kb.remove(QuadRef::new(
+            NamedNodeRef::new_unchecked("http://onto16.org/fus/zodiac/Leo"),
+            NamedNodeRef::new_unchecked("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+            NamedNodeRef::new_unchecked("http://onto16.org/fus/WesternSign"),
+            GraphNameRef::DefaultGraph,
+        ))
+        .unwrap();
+        assert!(verify_western_calendar(&kb).unwrap_err().contains("zodiac:Leo"));
+    }
+}
@@ -1,8 +1,16 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright © 2026 Maksim Zapevalov
 
-/// Онтологические уровни устойчивого существования
-#[derive(Debug, Clone, PartialEq)]
+use std::collections::BTreeMap;
+
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+
+use crate::iri::Iri;
+use crate::sparql;
+
+/// Онтологические уровни устойчивого существования (`fus:FUS_Level` в kb/fus/core.ttl)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum FusLevel {
     Ontological,    // Различение, Отношение, Позиция наблюдателя
     Physical,       // Энергия, Симметрия, Иерархия
@@ -11,14 +19,34 @@ pub enum FusLevel {
     Existential,    // Конечность, Диалог с небытием, Свобода-в-детерминизме
 }
 
+/// Все уровни, от самого глубокого
+pub const LEVELS: [FusLevel; 5] = [
+    FusLevel::Ontological,
+    FusLevel::Physical,
+    FusLevel::Biological,
+    FusLevel::Psychic,
+    FusLevel::Existential,
+];
+
 impl FusLevel {
-    pub fn uri(&self) -> &'static str {
+    /// IRI уровня в KB: `fus:OntologicalLevel`
+    pub fn uri(&self) -> Iri {
+        Iri::from_curie(&format!("fus:{}", self.name()))
+    }
+
+    /// Уровень по IRI ресурса KB
+    pub fn from_uri(uri: &Iri) -> Option<Self> {
+        LEVELS.into_iter().find(|level| level.uri() == *uri)
+    }
+
+    /// Локальное имя уровня: `OntologicalLevel`
+    pub fn name(&self) -> &'static str {
         match self {
-            FusLevel::Ontological => "http://onto16.org/fus#OntologicalLevel",
-            FusLevel::Physical => "http://onto16.org/fus#PhysicalLevel",
-            FusLevel::Biological => "http://onto16.org/fus#BiologicalLevel",
-            FusLevel::Psychic => "http://onto16.org/fus#PsychicLevel",
-            FusLevel::Existential => "http://onto16.org/fus#ExistentialLevel",
+            FusLevel::Ontological => "OntologicalLevel",
+            FusLevel::Physical => "PhysicalLevel",
+            FusLevel::Biological => "BiologicalLevel",
+            FusLevel::Psychic => "PsychicLevel",
+            FusLevel::Existential => "ExistentialLevel",
         }
     }
 
@@ -34,32 +62,189 @@ impl FusLevel {
     }
 }
 
-/// Базовые инварианты ФУС
-#[derive(Debug, Clone)]
+/// Инвариант ФУС, как он описан в KB
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Invariant {
-    pub id: String,
-    pub label: String,
+    pub id: Iri, // в JSON — CURIE, например: "fus:ObserverPosition"
+    pub label: String,       // русская метка, иначе английская, иначе CURIE
     pub level: FusLevel,
-    pub description: String,
+    pub description: String, // rdfs:comment, русский предпочтительнее
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>, // языковой тег ("" — без тега) → rdfs:label
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub comments: BTreeMap<String, String>, // языковой тег → rdfs:comment
 }
 
 impl Invariant {
     pub fn new(id: &str, label: &str, level: FusLevel, desc: &str) -> Self {
         Self {
-            id: id.to_string(),
+            id: Iri::from_curie(id),
             label: label.to_string(),
             level,
             description: desc.to_string(),
+            labels: BTreeMap::new(),
+            comments: BTreeMap::new(),
         }
     }
+
+    /// Метка на языке `lang` (`ru`, `en`), иначе основная
+    pub fn label_in(&self, lang: &str) -> &str {
+        self.labels.get(lang).unwrap_or(&self.label)
+    }
+
+    /// Описание на языке `lang`, иначе основное
+    pub fn description_in(&self, lang: &str) -> &str {
+        self.comments.get(lang).unwrap_or(&self.description)
+    }
 }
 
-/// Предопределённые инварианты (загружаются из kb/fus/*.ttl)
-pub const KNOWN_INVARIANTS: &[(&str, &str, FusLevel, &str)] = &[
-    ("fus:Distinction", "Различение", FusLevel::Ontological, "Способность различать 'бытие' и 'небытие'"),
-    ("fus:ObserverPosition", "Позиция наблюдателя", FusLevel::Ontological, "Независимость субъекта от объекта наблюдения"),
-    ("fus:EnergyHierarchy", "Энергетическая иерархия", FusLevel::Physical, "Энергия распределяется иерархически, а не хаотично"),
-    ("fus:Integrity", "Целостность", FusLevel::Biological, "Живая система сохраняет границу 'внутри/снаружи'"),
-    ("fus:AgencyResponsibility", "Агентность с ответственностью", FusLevel::Psychic, "Действие невозможно без этической ответственности"),
-    ("fus:FreedomInDeterminism", "Свобода-в-детерминизме", FusLevel::Existential, "Свобода возможна только внутри детерминированных рамок"),
-];
\ No newline at end of file
+/// Значение на предпочтительном языке: ru → en → без тега → любое
+fn preferred(values: &BTreeMap<String, String>) -> Option<&String> {
+    ["ru", "en", ""]
+        .iter()
+        .find_map(|lang| values.get(*lang))
+        .or_else(|| values.values().next())
+}
+
+// === Реестр инвариантов ===
+
+const INVARIANTS_QUERY: &str = r#"
+SELECT ?invariant ?level ?label ?comment WHERE {
+    ?invariant a fus:Invariant .
+    OPTIONAL {
+        { ?invariant fus:belongsToLevel ?level }
+        UNION { ?invariant rdfs:label ?label }
+        UNION { ?invariant rdfs:comment ?comment }
+    }
+}
+"#;
+
+/// Все инварианты `fus:Invariant` из загруженной KB.
+/// Строится при загрузке: инвариант, добавленный в Turtle, сразу доступен диагностике.
+#[derive(Debug, Clone, Default)]
+pub struct InvariantRegistry {
+    invariants: BTreeMap<Iri, Invariant>,
+}
+
+impl InvariantRegistry {
+    /// Реестр по хранилищу; инвариант без уровня или с неизвестным уровнем — ошибка загрузки
+    pub fn load(store: &Store) -> Result<Self, String> {
+        let mut levels: BTreeMap<Iri, Vec<Iri>> = BTreeMap::new();
+        let mut labels: BTreeMap<Iri, BTreeMap<String, String>> = BTreeMap::new();
+        let mut comments: BTreeMap<Iri, BTreeMap<String, String>> = BTreeMap::new();
+        for row in sparql::select(store, INVARIANTS_QUERY, &[])? {
+            let Some(invariant) = sparql::iri(&row, "invariant") else {
+                continue;
+            };
+            let invariant_levels = levels.entry(invariant.clone()).or_default();
+            if let Some(level) = sparql::iri(&row, "level") {
+                if !invariant_levels.contains(&level) {
+                    invariant_levels.push(level);
+                }
+            }
+            for (var, values) in [("label", &mut labels), ("comment", &mut comments)] {
+                if let Some(value) = sparql::literal(&row, var) {
+                    let lang = sparql::language(&row, var).unwrap_or_default();
+                    values.entry(invariant.clone()).or_default().entry(lang).or_insert(value);
+                }
+            }
+        }
+
+        let mut invariants = BTreeMap::new();
+        let mut errors = Vec::new();
+        for (id, found) in levels {
+            let level = match found.as_slice() {
+                [level] => FusLevel::from_uri(level).ok_or_else(|| format!("{}: неизвестный уровень ФУС {}", id, level)),
+                [] => Err(format!("{}: нет fus:belongsToLevel", id)),
+                _ => Err(format!("{}: несколько fus:belongsToLevel", id)),
+            };
+            let level = match level {
+                Ok(level) => level,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            let labels = labels.remove(&id).unwrap_or_default();
+            let comments = comments.remove(&id).unwrap_or_default();
+            invariants.insert(
+                id.clone(),
+                Invariant {
+                    label: preferred(&labels).cloned().unwrap_or_else(|| id.curie()),
+                    description: preferred(&comments).cloned().unwrap_or_default(),
+                    id,
+                    level,
+                    labels,
+                    comments,
+                },
+            );
+        }
+        if !errors.is_empty() {
+            return Err(format!("Инварианты в KB не согласованы: {}", errors.join("; ")));
+        }
+        Ok(Self { invariants })
+    }
+
+    pub fn get(&self, id: &Iri) -> Option<&Invariant> {
+        self.invariants.get(id)
+    }
+
+    /// Все инварианты, по IRI
+    pub fn iter(&self) -> impl Iterator<Item = &Invariant> {
+        self.invariants.values()
+    }
+
+    /// Инварианты одного уровня ФУС
+    pub fn by_level(&self, level: FusLevel) -> impl Iterator<Item = &Invariant> {
+        self.iter().filter(move |inv| inv.level == level)
+    }
+
+    pub fn len(&self) -> usize {
+        self.invariants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.invariants.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::packs;
+
+    const PREFIXES: &str = "@prefix fus: <http://onto16.org/fus/> .\n@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n";
+
+    fn load(data: &str) -> Result<InvariantRegistry, String> {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/fus.ttl", format!("{PREFIXES}{data}").as_bytes()).unwrap();
+        InvariantRegistry::load(&store)
+    }
+
+    #[test]
+    fn labels_and_level_come_from_the_kb() {
+        let registry = load(
+            r#"fus:Inv_Energy a fus:Invariant ; fus:belongsToLevel fus:PhysicalLevel ;
+                rdfs:label "Energy"@en, "Энергия"@ru ; rdfs:comment "Сохранение"@ru ."#,
+        )
+        .unwrap();
+        let inv = registry.get(&Iri::from_curie("fus:Inv_Energy")).unwrap();
+        assert_eq!((inv.level, inv.label.as_str(), inv.description.as_str()), (FusLevel::Physical, "Энергия", "Сохранение"));
+        assert_eq!(registry.by_level(FusLevel::Physical).count(), 1);
+    }
+
+    #[test]
+    fn level_must_be_single_and_known() {
+        let missing = load("fus:Inv_Lost a fus:Invariant .").unwrap_err();
+        assert!(missing.contains("fus:Inv_Lost: нет fus:belongsToLevel"), "{missing}");
+
+        let multiple = load(
+            "fus:Inv_Twice a fus:Invariant ; fus:belongsToLevel fus:PhysicalLevel, fus:PsychicLevel .",
+        )
+        .unwrap_err();
+        assert!(multiple.contains("fus:Inv_Twice: несколько fus:belongsToLevel"), "{multiple}");
+
+        let unknown = load("fus:Inv_Odd a fus:Invariant ; fus:belongsToLevel fus:AstralLevel .").unwrap_err();
+        assert!(unknown.contains("неизвестный уровень ФУС fus:AstralLevel"), "{unknown}");
+    }
+}
@@ -1,74 +1,254 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright © 2026 Maksim Zapevalov
+//
+// ФУС-диагностик: поиск нарушенных инвариантов по триггерным терминам KB
 
+use std::collections::{BTreeMap, BTreeSet};
+
+use oxigraph::sparql::QuerySolution;
 use oxigraph::store::Store;
-use oxigraph::sparql::QueryResults;
 use serde::{Deserialize, Serialize};
 
-use crate::core::fus::{FusLevel, Invariant, KNOWN_INVARIANTS};
+use crate::core::astro::Temperament;
+use crate::core::fus::{FusLevel, Invariant, InvariantRegistry};
+use crate::iri::Iri;
+use crate::lexicon::{Lang, StemmedText, TriggerTerm};
+use crate::sparql;
+use crate::trace::Trace;
 
-#[derive(Serialize, Deserialize)]
+/// Нарушение инварианта, выведенное из баз знаний
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Diagnosis {
     pub affected_level: FusLevel,
     pub violated_invariant: Invariant,
-    pub severity: f32, // 0.0–1.0
+    pub perturbation: Option<Iri>, // тип возмущения, если найден
+    pub severity: f32,                // 0.0–1.0
 }
 
-/// Диагностика: какие инварианты нарушены событием?
-pub fn diagnose_event(store: &Store, event_id: &str) -> Result<Diagnosis, String> {
-    // В реальной версии: SPARQL-запрос к store
-    // Здесь — упрощённая логика для демонстрации
-
-    // Эвристика: если в событии есть "ban", "AI", "school" → нарушение ObserverPosition
-    let mut max_severity = 0.0;
-    let mut best_invariant = None;
-    let mut best_level = FusLevel::Existential;
-
-    for (id, label, level, desc) in KNOWN_INVARIANTS.iter() {
-        let severity = estimate_severity(event_id, id);
-        if severity > max_severity {
-            max_severity = severity;
-            best_invariant = Some(Invariant::new(id, label, *level, desc));
-            best_level = *level;
+/// Порог серьёзности по умолчанию: достаточно одного совпавшего термина
+pub const DEFAULT_SEVERITY_CUTOFF: f32 = 0.5;
+
+/// Триггеры типов возмущений (через `pert:affectsInvariant`) и самих инвариантов.
+/// Инвариант и его уровень берутся из реестра (`InvariantRegistry`);
+/// сопоставление с текстом — в Rust, по основам слов (см. `lexicon`)
+const TRIGGER_QUERY: &str = r#"
+SELECT ?invariant ?perturbation ?term WHERE {
+    {
+        ?perturbation a pert:PerturbationType ;
+            pert:triggerTerm ?term ;
+            pert:affectsInvariant ?invariant .
+    } UNION {
+        ?invariant fus:triggerTerm ?term .
+    }
+}
+"#;
+
+/// Триггерные термины типов возмущений, без привязки к инвариантам
+const PERTURBATION_QUERY: &str = r#"
+SELECT ?perturbation ?term WHERE {
+    ?perturbation a pert:PerturbationType ;
+        pert:triggerTerm ?term .
+}
+"#;
+
+/// Триггер нарушения: термин инварианта или типа возмущения, затрагивающего инвариант
+#[derive(Debug, Clone)]
+struct InvariantTrigger {
+    invariant: Iri,
+    perturbation: Option<Iri>,
+    term: TriggerTerm,
+}
+
+/// Триггерные термины KB, приведённые к основам при загрузке KB
+#[derive(Debug, Clone, Default)]
+pub struct Triggers {
+    invariants: Vec<InvariantTrigger>,
+    perturbations: Vec<(Iri, TriggerTerm)>,
+}
+
+impl Triggers {
+    pub fn load(store: &Store) -> Result<Self, String> {
+        let term = |row: &QuerySolution| {
+            let lang = sparql::language(row, "term").and_then(|tag| Lang::from_tag(&tag));
+            Some(TriggerTerm::new(sparql::literal(row, "term")?, lang))
+        };
+        let mut triggers = Triggers::default();
+        for row in sparql::select(store, TRIGGER_QUERY, &[])? {
+            let (Some(invariant), Some(term)) = (sparql::iri(&row, "invariant"), term(&row)) else {
+                continue;
+            };
+            triggers.invariants.push(InvariantTrigger {
+                invariant,
+                perturbation: sparql::iri(&row, "perturbation"),
+                term,
+            });
         }
+        for row in sparql::select(store, PERTURBATION_QUERY, &[])? {
+            if let (Some(perturbation), Some(term)) = (sparql::iri(&row, "perturbation"), term(&row)) {
+                triggers.perturbations.push((perturbation, term));
+            }
+        }
+        Ok(triggers)
     }
+}
 
-    Ok(Diagnosis {
-        affected_level: best_level,
-        violated_invariant: best_invariant.unwrap_or_else(|| Invariant::new(
-            "fus:Unknown", "Неизвестный инвариант", FusLevel::Ontological, ""
-        )),
-        severity: max_severity,
-    })
+#[derive(Default)]
+struct Candidate {
+    terms: BTreeSet<String>,
+    perturbations: BTreeMap<Iri, usize>,
 }
 
-fn estimate_severity(event_id: &str, invariant_id: &str) -> f32 {
-    // Простейший матчинг по ключевым словам
-    let lower = event_id.to_lowercase();
-    match invariant_id {
-        "fus:ObserverPosition" => {
-            if lower.contains("ban") && (lower.contains("ai") || lower.contains("algorithm")) {
-                0.92
-            } else {
-                0.0
+/// Серьёзность по числу совпавших терминов: 1 → 0.5, 2 → 0.75, 3 → 0.875…
+fn severity(matched_terms: usize) -> f32 {
+    1.0 - 0.5f32.powi(matched_terms as i32)
+}
+
+/// Диагностика: какие инварианты нарушены событием?
+/// Возвращает нарушения с серьёзностью не ниже `cutoff`, от самого серьёзного;
+/// пустой список — ни один триггер KB не найден в тексте.
+/// При переданном `trace` записывает совпавшие термины, триплеты KB и шаги подсчёта.
+pub fn diagnose(
+    triggers: &Triggers,
+    invariants: &InvariantRegistry,
+    text: &str,
+    cutoff: f32,
+    mut trace: Option<&mut Trace>,
+) -> Vec<Diagnosis> {
+    let text = StemmedText::new(text);
+
+    let mut candidates: BTreeMap<Iri, Candidate> = BTreeMap::new();
+    for trigger in &triggers.invariants {
+        let invariant = &trigger.invariant;
+        // Ссылка на неизвестный инвариант — ошибка KB, её ловит валидатор
+        let Some(known) = invariants.get(invariant) else {
+            continue;
+        };
+        if !text.contains(&trigger.term) {
+            continue;
+        }
+
+        let (term, perturbation) = (&trigger.term.text, &trigger.perturbation);
+
+        if let Some(trace) = trace.as_deref_mut() {
+            let quoted = match &trigger.term.lang {
+                Some(Lang::Ru) => format!("\"{}\"@ru", term),
+                Some(Lang::En) => format!("\"{}\"@en", term),
+                None => format!("\"{}\"", term),
+            };
+            match perturbation {
+                Some(pert) => {
+                    trace.term(term, pert, "pert:triggerTerm");
+                    trace.triple(pert, "pert:triggerTerm", &quoted);
+                    trace.triple(pert, "pert:affectsInvariant", invariant);
+                }
+                None => {
+                    trace.term(term, invariant, "fus:triggerTerm");
+                    trace.triple(invariant, "fus:triggerTerm", &quoted);
+                }
             }
+            trace.triple(invariant, "fus:belongsToLevel", known.level.uri());
         }
-        "fus:AgencyResponsibility" => {
-            if lower.contains("manipulate") || lower.contains("control") {
-                0.85
-            } else {
-                0.0
+
+        let candidate = candidates.entry(invariant.clone()).or_default();
+        candidate.terms.insert(term.clone());
+        if let Some(pert) = perturbation {
+            *candidate.perturbations.entry(pert.clone()).or_default() += 1;
+        }
+    }
+
+    if let Some(trace) = trace {
+        for (invariant, candidate) in &candidates {
+            let n = candidate.terms.len();
+            let value = severity(n);
+            let verdict = if value >= cutoff { "принято" } else { "отсечено" };
+            trace.scoring_step(format!(
+                "{}: терминов {} → серьёзность 1 − 0.5^{} = {:.3}; порог {:.2} → {}",
+                invariant, n, n, value, cutoff, verdict
+            ));
+        }
+    }
+
+    let mut ranked = Vec::new();
+    for (invariant, candidate) in candidates {
+        let severity = severity(candidate.terms.len());
+        if severity < cutoff {
+            continue;
+        }
+        let Some(known) = invariants.get(&invariant) else {
+            continue;
+        };
+        ranked.push(Diagnosis {
+            affected_level: known.level,
+            violated_invariant: known.clone(),
+            perturbation: candidate
+                .perturbations
+                .into_iter()
+                .max_by(|(a_id, a), (b_id, b)| a.cmp(b).then(b_id.cmp(a_id)))
+                .map(|(pert, _)| pert),
+            severity,
+        });
+    }
+
+    // Больше совпавших терминов — серьёзнее нарушение; при равенстве порядок по IRI
+    ranked.sort_by(|a, b| {
+        b.severity
+            .total_cmp(&a.severity)
+            .then_with(|| a.violated_invariant.id.cmp(&b.violated_invariant.id))
+    });
+    ranked
+}
+
+/// Тип возмущения по триггерам `pert:triggerTerm`: тип с наибольшим числом
+/// совпавших терминов (при равенстве — первый по IRI); `None`, если совпадений нет
+pub fn match_perturbation(triggers: &Triggers, text: &str) -> Option<Iri> {
+    let text = StemmedText::new(text);
+    let mut counts: BTreeMap<&Iri, usize> = BTreeMap::new();
+    for (perturbation, term) in &triggers.perturbations {
+        if text.contains(term) {
+            *counts.entry(perturbation).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by(|(a_id, a), (b_id, b)| a.cmp(b).then(b_id.cmp(a_id)))
+        .map(|(pert, _)| pert.clone())
+}
+
+/// Тип возмущения события: из главного нарушения (`pert:affectsInvariant`),
+/// иначе по триггерам в заголовке; при переданном `trace` записывает, откуда он взят
+pub fn event_perturbation(
+    triggers: &Triggers,
+    primary: Option<&Diagnosis>,
+    title: &str,
+    trace: Option<&mut Trace>,
+) -> Option<Iri> {
+    if let Some(d) = primary {
+        if let Some(pert) = &d.perturbation {
+            if let Some(trace) = trace {
+                trace.scoring_step(format!(
+                    "Главное нарушение: {}; тип возмущения {} — по pert:affectsInvariant",
+                    d.violated_invariant.id, pert
+                ));
             }
+            return Some(pert.clone());
         }
-        _ => 0.0,
     }
+    let pert = match_perturbation(triggers, title);
+    if let (Some(trace), Some(pert)) = (trace, pert.as_ref()) {
+        trace.scoring_step(format!(
+            "Тип возмущения {} — по pert:triggerTerm в заголовке (инвариант не найден)",
+            pert
+        ));
+    }
+    pert
 }
 
-/// Реконструкция: предложить путь восстановления
-pub fn reconstruct(diagnosis: &Diagnosis, temperament: &str) -> String {
-    match diagnosis.violated_invariant.id.as_str() {
+/// Реконструкция: предложить путь восстановления.
+/// Без темперамента (нейтральный режим) — общий вариант, без адаптации под профиль.
+pub fn reconstruct(diagnosis: &Diagnosis, temperament: Option<Temperament>) -> String {
+    match diagnosis.violated_invariant.id.curie().as_str() {
         "fus:ObserverPosition" => {
-            if temperament == "choleric" {
+            if temperament == Some(Temperament::Choleric) {
                 "Восстановить право на независимую оценку: открыть исходный код, обеспечить прозрачность."
             } else {
                 "Обеспечить прозрачность алгоритма и право на объяснение решения."
@@ -76,4 +256,98 @@ pub fn reconstruct(diagnosis: &Diagnosis, temperament: &str) -> String {
         }
         _ => "Требуется этическая реконструкция в рамках ФУС-рамок.",
     }
-}
\ No newline at end of file
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxigraph::io::RdfFormat;
+
+    const TRIGGERS: &str = r#"
+        @prefix fus: <http://onto16.org/fus/> .
+        @prefix pert: <http://onto16.org/pert/> .
+
+        fus:Inv_Energy a fus:Invariant ; fus:belongsToLevel fus:PhysicalLevel .
+        fus:Inv_Agency a fus:Invariant ; fus:belongsToLevel fus:PsychicLevel ;
+            fus:triggerTerm "свобода"@ru .
+        pert:Leak a pert:PerturbationType ;
+            pert:triggerTerm "утечка"@ru, "данные"@ru ;
+            pert:affectsInvariant fus:Inv_Energy .
+        pert:Orphan a pert:PerturbationType ;
+            pert:triggerTerm "сирота"@ru ;
+            pert:affectsInvariant fus:Inv_Unknown .
+    "#;
+
+    fn setup() -> (Triggers, InvariantRegistry) {
+        let store = Store::new().unwrap();
+        store.load_from_reader(RdfFormat::Turtle, TRIGGERS.as_bytes()).unwrap();
+        (Triggers::load(&store).unwrap(), InvariantRegistry::load(&store).unwrap())
+    }
+
+    fn found(diagnoses: &[Diagnosis]) -> Vec<(&str, Option<&str>)> {
+        diagnoses
+            .iter()
+            .map(|d| (d.violated_invariant.id.local_name(), d.perturbation.as_ref().map(Iri::local_name)))
+            .collect()
+    }
+
+    #[test]
+    fn triggers_match_invariants_directly_and_via_perturbations() {
+        let (triggers, invariants) = setup();
+        let diagnose = |text| diagnose(&triggers, &invariants, text, DEFAULT_SEVERITY_CUTOFF, None);
+
+        let leak = diagnose("Утечка в дата-центре");
+        assert_eq!(found(&leak), [("Inv_Energy", Some("Leak"))]);
+        assert_eq!(leak[0].affected_level, FusLevel::Physical);
+        assert_eq!(found(&diagnose("Свобода слова")), [("Inv_Agency", None)]);
+        // Возмущение, затрагивающее неизвестный инвариант, нарушений не даёт
+        assert!(diagnose("Сирота").is_empty());
+        assert!(diagnose("Ничего не случилось").is_empty());
+    }
+
+    #[test]
+    fn ranking_follows_severity_and_cutoff() {
+        let (triggers, invariants) = setup();
+        let text = "Утечка: данные и свобода под угрозой";
+
+        let ranked = diagnose(&triggers, &invariants, text, DEFAULT_SEVERITY_CUTOFF, None);
+        assert_eq!(found(&ranked), [("Inv_Energy", Some("Leak")), ("Inv_Agency", None)]);
+        // 1 − 0.5^n: два термина — 0.75, один — 0.5
+        let severities: Vec<f32> = ranked.iter().map(|d| d.severity).collect();
+        assert_eq!(severities, [0.75, 0.5]);
+        assert_eq!(severity(3), 0.875);
+
+        // Порог выше одного термина отсекает второе нарушение
+        let strict = diagnose(&triggers, &invariants, text, 0.6, None);
+        assert_eq!(found(&strict), [("Inv_Energy", Some("Leak"))]);
+    }
+
+    #[test]
+    fn equal_severity_is_ordered_by_iri() {
+        let (triggers, invariants) = setup();
+        let ranked = diagnose(&triggers, &invariants, "Свобода и утечка", DEFAULT_SEVERITY_CUTOFF, None);
+        assert_eq!(found(&ranked), [("Inv_Agency", None), ("Inv_Energy", Some("Leak"))]);
+    }
+
+    #[test]
+    fn trace_records_terms_triples_and_cut_candidates() {
+        let (triggers, invariants) = setup();
+        let mut trace = Trace::default();
+        let ranked = diagnose(&triggers, &invariants, "Утечка: данные и свобода под угрозой", 0.6, Some(&mut trace));
+        assert_eq!(found(&ranked), [("Inv_Energy", Some("Leak"))]);
+
+        let terms: Vec<(&str, &str)> = trace
+            .matched_terms
+            .iter()
+            .map(|m| (m.resource.as_str(), m.property.as_str()))
+            .collect();
+        assert!(terms.contains(&("pert:Leak", "pert:triggerTerm")));
+        assert!(terms.contains(&("fus:Inv_Agency", "fus:triggerTerm")));
+        assert!(trace.kb_triples.contains(&"pert:Leak pert:affectsInvariant fus:Inv_Energy".to_string()));
+        assert!(trace.kb_triples.contains(&"fus:Inv_Agency fus:belongsToLevel fus:PsychicLevel".to_string()));
+        // Отсечённый порогом кандидат остаётся в журнале подсчёта
+        assert_eq!(trace.scoring.len(), 2);
+        assert!(trace.scoring.iter().any(|s| s.starts_with("fus:Inv_Agency") && s.ends_with("отсечено")));
+    }
+}
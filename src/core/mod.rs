@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Доменная модель ФУС: уровни и инварианты, диагностика, операторы, профиль субъекта
+
+pub mod astro;
+pub mod fus;
+pub mod inference;
+pub mod normative;
+pub mod operators;
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Нормативный фильтр реконструкции: действия, предложенные для восстановления
+// инварианта, сверяются с коллективной памятью (`kb/optional/collective-memory.ttl`).
+// Травма, запрещающая действие, отклоняет его; запрет другого фрейма памяти
+// (канон, ритуал, архивный долг) — отмечает. Без модуля памяти фильтр ничего не запрещает.
+
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+
+use crate::iri::Iri;
+use crate::knowledge::packs;
+use crate::sparql;
+use crate::trace::Trace;
+
+/// Решение фильтра по действию
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Allowed,
+    Flagged, // запрет фрейма памяти, не являющегося травмой: предложить с оговоркой
+    Vetoed,  // запрет коллективной травмы: не предлагать
+}
+
+/// Фрейм памяти, заблокировавший или отметивший действие
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MemoryCitation {
+    pub frame: Iri,
+    pub kind: Iri, // memory:CollectiveTrauma, memory:Canon…
+    pub label: Option<String>,
+    pub references: Vec<String>, // memory:historicalReference
+    pub file: String,            // пакет, в котором записан запрет
+}
+
+/// Действие реконструкции после нормативного фильтра
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Suggestion {
+    pub action: Iri,
+    pub label: Option<String>,
+    pub verdict: Verdict,
+    pub citations: Vec<MemoryCitation>,
+}
+
+const ACTIONS_QUERY: &str = r#"
+SELECT ?pert ?action (SAMPLE(?l) AS ?label) WHERE {
+    ?pert pert:suggestedAction ?action .
+    OPTIONAL { ?action rdfs:label ?l }
+}
+GROUP BY ?pert ?action
+ORDER BY ?action
+"#;
+
+const FORBIDS_QUERY: &str = r#"
+SELECT ?action ?frame ?kind ?graph (SAMPLE(?l) AS ?label) (GROUP_CONCAT(DISTINCT ?ref; SEPARATOR="\n") AS ?refs) WHERE {
+    GRAPH ?graph { ?frame memory:forbidsAction ?action }
+    ?frame a ?kind .
+    ?kind rdfs:subClassOf memory:MemoryFrame .
+    OPTIONAL { ?frame rdfs:label ?l }
+    OPTIONAL { ?frame memory:historicalReference ?ref }
+}
+GROUP BY ?action ?frame ?kind ?graph
+ORDER BY ?frame ?kind
+"#;
+
+/// Действия, которые KB предлагает против возмущения (`pert:suggestedAction`)
+pub fn suggested_actions(store: &Store, perturbation: &Iri) -> Result<Vec<(Iri, Option<String>)>, String> {
+    let rows = sparql::select(store, ACTIONS_QUERY, &[("pert", perturbation.term())])?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| Some((sparql::iri(row, "action")?, sparql::literal(row, "label"))))
+        .collect())
+}
+
+/// Фреймы памяти, запрещающие действие
+pub fn forbidding_frames(store: &Store, action: &Iri) -> Result<Vec<MemoryCitation>, String> {
+    let mut citations: Vec<MemoryCitation> = Vec::new();
+    for row in sparql::select(store, FORBIDS_QUERY, &[("action", action.term())])? {
+        let (Some(frame), Some(kind), Some(graph)) =
+            (sparql::iri(&row, "frame"), sparql::iri(&row, "kind"), sparql::iri(&row, "graph"))
+        else {
+            continue;
+        };
+        // Фрейм — одна цитата, даже если запрет выведен и в другом графе
+        if citations.iter().any(|c| c.frame == frame && c.kind == kind) {
+            continue;
+        }
+        citations.push(MemoryCitation {
+            frame,
+            kind,
+            label: sparql::literal(&row, "label"),
+            references: sparql::literal(&row, "refs")
+                .map(|refs| refs.lines().map(str::to_string).collect())
+                .unwrap_or_default(),
+            file: packs::pack_name(graph.as_str()).unwrap_or(graph.as_str()).to_string(),
+        });
+    }
+    Ok(citations)
+}
+
+/// Нормативный фильтр: действия против возмущения с решением и цитатами фреймов памяти
+pub fn review(store: &Store, perturbation: &Iri, mut trace: Option<&mut Trace>) -> Result<Vec<Suggestion>, String> {
+    let trauma = Iri::from_curie("memory:CollectiveTrauma");
+    let mut suggestions = Vec::new();
+    for (action, label) in suggested_actions(store, perturbation)? {
+        let citations = forbidding_frames(store, &action)?;
+        let verdict = if citations.iter().any(|c| c.kind == trauma) {
+            Verdict::Vetoed
+        } else if !citations.is_empty() {
+            Verdict::Flagged
+        } else {
+            Verdict::Allowed
+        };
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.triple(perturbation, "pert:suggestedAction", &action);
+            for c in &citations {
+                trace.triple(&c.frame, "memory:forbidsAction", &action);
+            }
+        }
+        suggestions.push(Suggestion {
+            action,
+            label,
+            verdict,
+            citations,
+        });
+    }
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTIONS: &str = r#"
+        @prefix pert: <http://onto16.org/pert/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+        pert:Leak pert:suggestedAction pert:Censor, pert:Forget, pert:Repair .
+        pert:Censor rdfs:label "Цензура" .
+    "#;
+
+    const MEMORY: &str = r#"
+        @prefix pert: <http://onto16.org/pert/> .
+        @prefix memory: <http://onto16.org/memory/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+        memory:CollectiveTrauma rdfs:subClassOf memory:MemoryFrame .
+        memory:ArchivalDuty rdfs:subClassOf memory:MemoryFrame .
+
+        memory:Trauma a memory:CollectiveTrauma ; rdfs:label "Травма" ;
+            memory:historicalReference "Источник 1", "Источник 2" ;
+            memory:forbidsAction pert:Censor .
+        memory:Duty a memory:ArchivalDuty ;
+            memory:forbidsAction pert:Forget .
+    "#;
+
+    fn setup() -> Store {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/actions.ttl", ACTIONS.as_bytes()).unwrap();
+        packs::load_pack(&store, "optional/memory.ttl", MEMORY.as_bytes()).unwrap();
+        store
+    }
+
+    #[test]
+    fn verdict_follows_the_forbidding_frame() {
+        let store = setup();
+        let suggestions = review(&store, &Iri::from_curie("pert:Leak"), None).unwrap();
+        let verdicts: Vec<(&str, Verdict)> = suggestions.iter().map(|s| (s.action.local_name(), s.verdict)).collect();
+        assert_eq!(
+            verdicts,
+            [("Censor", Verdict::Vetoed), ("Forget", Verdict::Flagged), ("Repair", Verdict::Allowed)]
+        );
+
+        let vetoed = &suggestions[0];
+        assert_eq!(vetoed.label.as_deref(), Some("Цензура"));
+        assert_eq!(vetoed.citations.len(), 1);
+        let citation = &vetoed.citations[0];
+        assert_eq!(citation.frame, Iri::from_curie("memory:Trauma"));
+        assert_eq!(citation.kind, Iri::from_curie("memory:CollectiveTrauma"));
+        assert_eq!(citation.label.as_deref(), Some("Травма"));
+        let mut references = citation.references.clone();
+        references.sort();
+        assert_eq!(references, ["Источник 1", "Источник 2"]);
+        assert_eq!(citation.file, "optional/memory.ttl");
+
+        let flagged = &suggestions[1].citations;
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].kind, Iri::from_curie("memory:ArchivalDuty"));
+        assert_eq!(flagged[0].label, None);
+        assert!(suggestions[2].citations.is_empty());
+    }
+}
@@ -2,91 +2,195 @@
 // Copyright © 2026 Maksim Zapevalov (max456im)
 //
 // Онтологический движок NoemaFeed Mobile
-// Координирует: загрузку KB → диагностику ФУС → реконструкцию
+// Координирует: загрузку KB → профиль → диагностику ФУС → реконструкцию
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 use oxigraph::store::Store;
-use oxigraph::io::{RdfFormat, RdfParser};
-use std::io::Cursor;
+use serde::{Deserialize, Serialize};
 
-/// Уровни ФУС (упрощённо)
-#[derive(Debug, Clone)]
-pub enum FusLevel {
-    Ontological,
-    Structural,
-    Archetypal,
-    Identity,
-    Existential,
-}
+use crate::context::analogs::{self, AnalogReport};
+use crate::context::cycles::{self, Cycle, CyclePosition};
+use crate::context::ontogenesis::{self, PhaseMachine, PhaseTrack, TransitionOutlook};
+use crate::context::frames::{self, CauseFrame, EffectFrame};
+use crate::context::{self, IndexedEvent};
+use crate::core::astro::{self, Profile, Temperament};
+use crate::core::inference::{self, Diagnosis, Triggers};
+use crate::core::normative::{self, Suggestion, Verdict};
+use crate::core::fus::{self, FusLevel, InvariantRegistry};
+use crate::iri::{self, Iri};
+use crate::knowledge::manifest::{self, KbManifest};
+use crate::knowledge::events::{self, EventRecord};
+use crate::knowledge::packs::{self, PackInfo};
+use crate::knowledge::query::{self, QueryLimits, QueryOutput};
+use crate::knowledge::reasoner::Entailment;
+use crate::knowledge::rules::{self, RuleReport};
+use crate::knowledge::validate::{self, ShapeViolation};
+use crate::trace::Trace;
 
-impl FusLevel {
-    pub fn uri(&self) -> &'static str {
-        match self {
-            FusLevel::Ontological => "fus:OntologicalLevel",
-            FusLevel::Structural => "fus:StructuralLevel",
-            FusLevel::Archetypal => "fus:ArchetypalLevel",
-            FusLevel::Identity => "fus:IdentityLevel",
-            FusLevel::Existential => "fus:ExistentialLevel",
-        }
-    }
+// === Загрузка баз знаний ===
 
-    pub fn from_str(s: &str) -> Self {
-        match s {
-            "Ontological" => FusLevel::Ontological,
-            "Structural" => FusLevel::Structural,
-            "Archetypal" => FusLevel::Archetypal,
-            "Identity" => FusLevel::Identity,
-            _ => FusLevel::Existential,
-        }
-    }
+/// Встроенные базы знаний: (путь относительно kb/, содержимое) — «10 баз» из KB_SPEC.md
+/// и правила вывода. Каждая загружается пакетом в свой именованный граф (см. `knowledge::packs`).
+pub const KB_FILES: &[(&str, &str)] = &[
+    // === ФУС-ядро ===
+    ("fus/core.ttl", include_str!("../kb/fus/core.ttl")),
+    ("fus/structural.ttl", include_str!("../kb/fus/structural.ttl")),
+    ("fus/archetypal.ttl", include_str!("../kb/fus/archetypal.ttl")),
+    ("fus/identity.ttl", include_str!("../kb/fus/identity.ttl")),
+    ("fus/existential.ttl", include_str!("../kb/fus/existential.ttl")),
+    // === Профили субъектов ===
+    ("subjects/subject-registry.ttl", include_str!("../kb/subjects/subject-registry.ttl")),
+    ("subjects/astro-profiles.ttl", include_str!("../kb/subjects/astro-profiles.ttl")),
+    // === Динамические библиотеки ===
+    ("dynamics/perturbations.ttl", include_str!("../kb/dynamics/perturbations.ttl")),
+    ("dynamics/ontogenesis.ttl", include_str!("../kb/dynamics/ontogenesis.ttl")),
+    ("dynamics/context-index.ttl", include_str!("../kb/dynamics/context-index.ttl")),
+    // === Правила вывода ===
+    ("dynamics/rules.ttl", include_str!("../kb/dynamics/rules.ttl")),
+];
+
+/// Загруженная KB: хранилище и то, что вычисляется по нему один раз при загрузке
+pub struct KnowledgeBase {
+    pub store: Store,
+    pub invariants: InvariantRegistry,
+    pub entailment: Entailment, // итог вывода RDFS/OWL-lite в граф `reasoner::INFERRED_GRAPH`
+    pub rules: RuleReport,      // что вывели правила KB (граф `rules::RULES_GRAPH`)
+    pub manifest: KbManifest,
+    pub triggers: Triggers, // триггерные термины KB, приведённые к основам
+    pub index: BTreeMap<Iri, IndexedEvent>, // контекстуальный индекс; пересобирается при добавлении события
+    pub cycles: BTreeMap<Iri, Cycle>,       // циклы индекса с оценкой периода; пересобираются вместе с ним
+    pub phases: PhaseMachine,               // автомат фаз онтогенеза
 }
 
-/// Результат диагностики события
-#[derive(Debug, Clone)]
-pub struct Diagnosis {
-    pub affected_level: FusLevel,
-    pub violated_invariant: String, // URI инварианта, например: "fus:ObserverPosition"
+/// Исходник пакета: имя и Turtle (встроенный или переданный во время работы)
+type PackSource = (String, Cow<'static, [u8]>);
+
+fn builtin_packs() -> Vec<PackSource> {
+    KB_FILES
+        .iter()
+        .map(|(name, ttl)| (name.to_string(), Cow::Borrowed(ttl.as_bytes())))
+        .collect()
 }
 
 /// Загружает все локальные базы знаний в единое хранилище
-pub fn load_knowledge_base() -> Result<Store, Box<dyn std::error::Error>> {
+pub fn load_knowledge_base() -> Result<KnowledgeBase, Box<dyn std::error::Error>> {
+    build_kb(&builtin_packs(), &[])
+}
+
+/// Новая KB из пакетов с проверкой согласованности
+fn build_kb(sources: &[PackSource], events: &[EventRecord]) -> Result<KnowledgeBase, Box<dyn std::error::Error>> {
     let store = Store::new()?;
+    for (name, data) in sources {
+        packs::load_pack(&store, name, data)?;
+    }
+    for event in events {
+        events::add_event(&store, event)?;
+    }
+    // Иерархии классов и свойств и правила KB: запросы ядра опираются на выведенные факты
+    let (entailment, rules) = rules::infer(&store)?;
 
-    // === ФУС-ядро ===
-    load_ttl_from_str(&store, include_str!("../kb/fus/core.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/fus/structural.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/fus/archetypal.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/fus/identity.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/fus/existential.ttl"))?;
+    // Расхождения астро-KB (темпераменты, календарь знаков) — ошибка загрузки, а не тихий откат
+    astro::verify(&store)?;
+    verify_references(&store)?;
+    let invariants = InvariantRegistry::load(&store)?;
+    let index = context::index(&store)?;
+    Ok(KnowledgeBase {
+        manifest: manifest::build(&store)?,
+        triggers: Triggers::load(&store)?,
+        cycles: cycles::load(&store, &index)?,
+        index,
+        phases: PhaseMachine::load(&store)?,
+        store,
+        invariants,
+        entailment,
+        rules,
+    })
+}
 
-    // === Профили субъектов ===
-    load_ttl_from_str(&store, include_str!("../kb/subjects/subject-registry.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/subjects/astro-profiles.ttl"))?;
+// === Ссылки кода на KB ===
 
-    // === Динамические библиотеки ===
-    load_ttl_from_str(&store, include_str!("../kb/dynamics/perturbations.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/dynamics/ontogenesis.ttl"))?;
-    load_ttl_from_str(&store, include_str!("../kb/dynamics/context-index.ttl"))?;
+/// Классы и свойства KB, на которых построены запросы ядра
+const VOCABULARY: &[&str] = &[
+    "rdfs:label",
+    "rdfs:comment",
+    "fus:FUS_Level",
+    "fus:Invariant",
+    "fus:belongsToLevel",
+    "fus:triggerTerm",
+    "fus:Temperament",
+    "fus:WesternSign",
+    "fus:ChineseSign",
+    "fus:element",
+    "fus:quality",
+    "fus:startsOn",
+    "fus:endsOn",
+    "fus:mapsToTemperament",
+    "pert:PerturbationType",
+    "pert:triggerTerm",
+    "pert:affectsInvariant",
+    "pert:suggestedAction",
+    "onto:Phase",
+    "onto:InitialPhase",
+    "onto:Transition",
+    "onto:from",
+    "onto:to",
+    "onto:triggeredBy",
+];
 
-    Ok(store)
+/// Все IRI, на которые ссылается код: словарь запросов и ресурсы перечислений ядра
+pub fn referenced_iris() -> Vec<Iri> {
+    let mut iris: Vec<Iri> = VOCABULARY.iter().map(|curie| Iri::from_curie(curie)).collect();
+    iris.extend(fus::LEVELS.iter().map(FusLevel::uri));
+    iris.push(Iri::from_curie("fus:ObserverPosition")); // особая реконструкция в `inference::reconstruct`
+    iris.extend(astro::ELEMENTS.iter().map(astro::Element::uri));
+    iris.extend(astro::QUALITIES.iter().map(astro::Quality::uri));
+    iris.extend(astro::TEMPERAMENTS.iter().map(Temperament::uri));
+    iris.extend(astro::WESTERN_SIGNS.iter().map(astro::WesternSign::uri));
+    iris.extend(astro::ANIMALS.iter().map(astro::ChineseAnimal::uri));
+    iris
 }
 
-fn load_ttl_from_str(store: &Store, ttl: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let parser = RdfParser::from_format(RdfFormat::Turtle);
-    store.load_from_read(parser, Cursor::new(ttl.as_bytes()))?;
-    Ok(())
+/// Каждый IRI из кода должен существовать в KB: иначе поиск по нему
+/// молча возвращал бы пустой результат
+pub fn verify_references(store: &Store) -> Result<(), String> {
+    let missing = iri::missing(store, &referenced_iris())?;
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        let missing: Vec<String> = missing.iter().map(Iri::curie).collect();
+        Err(format!("Код ссылается на ресурсы, которых нет в KB: {}", missing.join(", ")))
+    }
 }
 
+// === Типы данных ===
+
 /// Представление события для анализа
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NewsEvent {
+    #[serde(default)]
     pub id: String,
     pub title: String,
     pub content: Option<String>,
-    pub source: Option<String>,
+    pub source_url: Option<String>,
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub actors: Vec<Iri>, // участники (temporal:involvedActor), если известны: "wd:Q142"
+}
+
+impl NewsEvent {
+    /// Текст для диагностики: заголовок и содержание
+    pub fn text(&self) -> String {
+        match &self.content {
+            Some(content) => format!("{}\n{}", self.title, content),
+            None => self.title.clone(),
+        }
+    }
 }
 
 /// Режим анализа
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AnalysisMode {
     Constructive,   // 🟢
     Analytical,     // 🔵
@@ -94,7 +198,7 @@ pub enum AnalysisMode {
 }
 
 impl AnalysisMode {
-    pub fn from_str(s: &str) -> Self {
+    pub fn parse(s: &str) -> Self {
         match s.trim().to_lowercase().as_str() {
             "constructive" => Self::Constructive,
             "analytical" => Self::Analytical,
@@ -102,6 +206,14 @@ impl AnalysisMode {
         }
     }
 
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalysisMode::Constructive => "constructive",
+            AnalysisMode::Analytical => "analytical",
+            AnalysisMode::Critical => "critical",
+        }
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             AnalysisMode::Constructive => "Конструктивный режим",
@@ -111,105 +223,647 @@ impl AnalysisMode {
     }
 }
 
+/// Параметры анализа (все поля необязательны)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AnalysisOptions {
+    pub severity_cutoff: f32,
+    pub explain: bool,       // добавить трассировку вывода в результат
+    pub max_analogs: usize, // исторических аналогов в результате
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            severity_cutoff: inference::DEFAULT_SEVERITY_CUTOFF,
+            explain: false,
+            max_analogs: analogs::DEFAULT_MAX_ANALOGS,
+        }
+    }
+}
+
 /// Полный результат анализа
 #[derive(Debug, Clone)]
 pub struct OntoScene {
     pub event_id: String,
     pub mode: AnalysisMode,
-    pub affected_level: FusLevel,
-    pub violated_invariant: String,
+    pub violations: Vec<Diagnosis>, // все нарушения, от самого серьёзного
     pub perturbation_type: String,
-    pub temperament_hint: String,
-    pub reconstruction: String,
+    pub temperament: Option<Temperament>, // None — нейтральный режим, без персонализации
+    pub reconstruction: Option<String>,   // None — нарушений не найдено
+    pub suggestions: Vec<Suggestion>,     // действия реконструкции после нормативного фильтра
+    pub analogs: AnalogReport,            // исторические аналоги из контекстуального индекса
+    pub cycles: Vec<CyclePosition>,       // циклы события или его аналогов и место события в них
+    pub phases: Vec<PhaseTrack>,          // фазы онтогенеза акторов и циклов события
+    pub outlook: Vec<TransitionOutlook>,  // вероятный выход из фаз для темперамента читателя
     pub summary: String,
+    pub knowledge: Vec<String>,   // пакеты KB, на которых построен анализ: «имя@версия»
+    pub kb_problems: Vec<String>, // проблемы манифеста: отсутствующие пакеты, версии, заголовки
+    pub trace: Option<Trace>,
 }
 
-/// Диагностика события по заголовку (заглушка вместо SPARQL)
-pub fn diagnose_event(_store: &Store, title: &str) -> Result<Diagnosis, Box<dyn std::error::Error>> {
-    // Простейшая эвристика: если есть "наблюдатель", "позиция" → нарушение ObserverPosition
-    let title_lower = title.to_lowercase();
-    if title_lower.contains("наблюд") || title_lower.contains("observer") {
-        Ok(Diagnosis {
-            affected_level: FusLevel::Ontological,
-            violated_invariant: "fus:ObserverPosition".to_string(),
-        })
-    } else if title_lower.contains("структура") || title_lower.contains("system") {
-        Ok(Diagnosis {
-            affected_level: FusLevel::Structural,
-            violated_invariant: "fus:SystemIntegrity".to_string(),
-        })
+impl OntoScene {
+    /// Главное нарушение — первое в ранжированном списке
+    pub fn primary(&self) -> Option<&Diagnosis> {
+        self.violations.first()
+    }
+}
+
+// === Персонализация ===
+
+/// Подсказка темперамента в нейтральном режиме
+pub const NEUTRAL_HINT: &str = "neutral";
+
+pub fn temperament_hint(temperament: Option<Temperament>) -> &'static str {
+    temperament.map(|t| t.as_str()).unwrap_or(NEUTRAL_HINT)
+}
+
+/// Строка сводки о персонализации по подсказке темперамента
+pub fn personalization_line(hint: &str) -> String {
+    if hint == NEUTRAL_HINT {
+        "Персонализация: не применялась (профиль не задан)".to_string()
     } else {
-        Ok(Diagnosis {
-            affected_level: FusLevel::Existential,
-            violated_invariant: "fus:FreedomInDeterminism".to_string(),
-        })
+        format!("Для темперамента: {}", hint)
     }
 }
 
-/// Генерация реконструкции (заглушка)
-pub fn reconstruct(diagnosis: &Diagnosis, temperament: &str) -> String {
+// === Конвейер ===
+
+/// Строка вывода о вероятном выходе из фазы для темперамента читателя
+fn outlook_line(o: &TransitionOutlook) -> String {
+    let temperament = o.temperament_label.as_deref().unwrap_or(o.temperament.local_name());
+    let from = o.from_label.as_deref().unwrap_or(o.from.local_name());
+    match (&o.to, o.regressive) {
+        (None, _) => format!(
+            "\nПрогноз ({}): для выхода из «{}» KB не задаёт предпочтений этого темперамента",
+            temperament, from
+        ),
+        (Some(to), regressive) => format!(
+            "\nПрогноз ({}): из «{}» вероятен переход в «{}»{}",
+            temperament,
+            from,
+            o.to_label.as_deref().unwrap_or(to.local_name()),
+            if regressive { " — регрессивная ветвь" } else { "" }
+        ),
+    }
+}
+
+/// Предупреждение к реконструкции, когда вероятная ветвь темперамента регрессивна;
+/// фаза без метки называется по IRI
+fn regressive_warning(o: &TransitionOutlook) -> String {
     format!(
-        "Реконструкция для {} при нарушении {}: адаптировать нарратив под {} темперамент.",
-        diagnosis.affected_level.uri(),
-        diagnosis.violated_invariant,
-        temperament
+        " Осторожно: для темперамента «{}» типичен выход из кризиса через «{}» — выбирайте действия, возвращающие инвариант, а не контроль.",
+        o.temperament_label.as_deref().unwrap_or(o.temperament.local_name()),
+        o.to_label.as_deref().or(o.to.as_ref().map(Iri::local_name)).unwrap_or_default()
     )
 }
 
-/// Определяет темперамент по году рождения (китайский гороскоп → элемент → темперамент)
-fn determine_temperament(year: u32) -> String {
-    match year % 12 {
-        5 | 6 => "choleric",     // Змея, Лошадь → Огонь
-        3 | 4 => "sanguine",    // Кролик, Дракон → Дерево
-        7 | 8 => "phlegmatic",  // Коза, Обезьяна → Земля
-        _ => "melancholic",     // Остальные
-    }.to_string()
+/// Строка вывода о фазе онтогенеза темы: «второй кризис, а не первый»
+fn phase_line(track: &PhaseTrack, event: Option<&Iri>) -> String {
+    let subject = track.subject_label.as_deref().unwrap_or(track.subject.local_name());
+    let phase = track.phase_label.as_deref().unwrap_or(track.phase.local_name());
+    let changed = track.history.last().is_some_and(|step| step.event.as_ref() == event);
+    let visit = match track.visit {
+        0 | 1 => String::new(),
+        n => format!(", {}-й раз", n),
+    };
+    if changed {
+        format!("\nФаза ({}): переход в «{}»{}", subject, phase, visit)
+    } else {
+        format!("\nФаза ({}): «{}»{} — без изменений", subject, phase, visit)
+    }
+}
+
+/// Строка вывода о цикле: период, разброс и место события в цикле
+fn cycle_line(p: &CyclePosition) -> String {
+    let name = p.cycle.label.as_deref().unwrap_or(p.cycle.id.local_name());
+    let Some(period) = p.cycle.period else {
+        return format!("\nЦикл: {} — период не оценён: меньше двух датированных событий", name);
+    };
+    let spread = match p.cycle.std_dev {
+        Some(std_dev) => format!(" ± {:.1} г.", std_dev),
+        None => " г. (по одному интервалу)".to_string(),
+    };
+    let mut line = format!("\nЦикл: {} повторяется примерно каждые {:.1}{}", name, period, spread);
+    if let (Some(elapsed), Some(phase)) = (p.elapsed, p.phase) {
+        line.push_str(&format!("; с прошлого события прошло {:.1} г. ({:.0}% периода)", elapsed, phase * 100.0));
+    }
+    if let Some(next) = &p.next_expected {
+        line.push_str(&format!("; следующее ожидается около {}", next));
+    }
+    line
+}
+
+/// Признаки новости для контекстуального индекса: событие из индекса — со всеми его
+/// признаками, иначе — тип возмущения и известные акторы
+fn news_features(index: &BTreeMap<Iri, IndexedEvent>, event: &NewsEvent, perturbation: Option<Iri>) -> IndexedEvent {
+    let mut features = Iri::new(&event.id)
+        .ok()
+        .and_then(|id| index.get(&id).cloned())
+        .unwrap_or_else(|| IndexedEvent {
+            label: Some(event.title.clone()),
+            occurred_at: event.published_at.clone(),
+            ..Default::default()
+        });
+    features.actions.extend(perturbation);
+    features.actors.extend(event.actors.iter().cloned());
+    features
 }
 
 /// Основная функция: построение онтосцены
 pub fn build_onto_scene(
-    store: &Store,
+    kb: &KnowledgeBase,
     event: &NewsEvent,
     mode: AnalysisMode,
-    birth_year: u32,
+    profile: &Profile,
+    options: &AnalysisOptions,
 ) -> Result<OntoScene, Box<dyn std::error::Error>> {
-    // 1. Диагностика ФУС
-    let diagnosis = diagnose_event(store, &event.title)?;
+    let store = &kb.store;
+    let mut trace = options.explain.then(Trace::default);
 
-    // 2. Определение темперамента
-    let temperament = determine_temperament(birth_year);
+    // 1. Темперамент по профилю; нейтральный профиль — без персонализации
+    let temperament = profile.temperament(store, trace.as_mut())?;
 
-    // 3. Сопоставление с типом возмущения (упрощённо)
-    let pert_type = if event.title.to_lowercase().contains("ban") && event.title.to_lowercase().contains("ai") {
-        "pert:RegulatoryPrecautionism".to_string()
-    } else if event.title.to_lowercase().contains("manipulat") {
-        "pert:AlgorithmicManipulation".to_string()
-    } else {
-        "pert:GenericDisturbance".to_string()
+    // 2. Диагностика ФУС по триггерам KB: ранжированный список нарушений
+    let violations = inference::diagnose(&kb.triggers, &kb.invariants, &event.text(), options.severity_cutoff, trace.as_mut());
+    let primary = violations.first();
+
+    // 3. Тип возмущения: из главного нарушения, иначе по заголовку
+    let perturbation = inference::event_perturbation(&kb.triggers, primary, &event.title, trace.as_mut());
+    let perturbation_type = perturbation
+        .as_ref()
+        .map(Iri::curie)
+        .unwrap_or_else(|| "pert:GenericDisturbance".to_string());
+
+    // 4. Реконструкция и нормативный фильтр коллективной памяти
+    let mut reconstruction = primary.map(|d| inference::reconstruct(d, temperament));
+    let suggestions = match (primary, perturbation.as_ref()) {
+        (Some(_), Some(pert)) => normative::review(store, pert, trace.as_mut())?,
+        _ => Vec::new(),
     };
 
-    // 4. Реконструкция
-    let reconstruction = reconstruct(&diagnosis, &temperament);
+    // 5. Исторические аналоги: событие из индекса — со всеми его признаками,
+    // иначе — по типу возмущения и известным акторам
+    let index = &kb.index;
+    let features = news_features(index, event, perturbation.clone());
+    let analogs = analogs::find(&features, index, options.max_analogs, trace.as_mut());
+    let cycles = cycles::locate(&kb.cycles, index, &features, &analogs, trace.as_mut())?;
+    // Фазы онтогенеза тем события: его акторов и циклов
+    let subjects = features
+        .actors
+        .iter()
+        .cloned()
+        .chain(cycles.iter().map(|p| p.cycle.id.clone()))
+        .collect();
+    let machine = &kb.phases;
+    let phases = ontogenesis::locate(store, machine, index, &features, &subjects, trace.as_mut())?;
 
-    // 5. Формирование вывода
-    let level_label = diagnosis.affected_level.uri().split('#').last().unwrap_or("Unknown");
-    let summary = format!(
-        "[NoemaFeed • {}]\nНарушение: {} ({})\nТип возмущения: {}\nДля темперамента: {}",
+    // Прогноз выхода из фаз по темпераменту: фазы тем события, а без тем —
+    // фаза, в которую возмущение переводит стабильную систему
+    let mut from: Vec<Iri> = phases.iter().map(|t| t.phase.clone()).collect();
+    if from.is_empty() {
+        from.extend(perturbation.iter().filter_map(|pert| {
+            machine.target(machine.initial(), &[pert.clone()].into()).cloned()
+        }));
+    }
+    from.sort();
+    from.dedup();
+    let outlook: Vec<TransitionOutlook> = match temperament {
+        Some(t) => from.iter().filter_map(|phase| machine.outlook(phase, t)).collect(),
+        None => Vec::new(),
+    };
+    if let Some(trace) = trace.as_mut() {
+        for o in &outlook {
+            match &o.transition {
+                Some(transition) => trace.triple(transition, "onto:preferredForTemperament", &o.temperament),
+                None => trace.scoring_step(format!("Переход из {} для {} в KB не задан", o.from, o.temperament)),
+            }
+        }
+    }
+    if let (Some(text), Some(o)) = (reconstruction.as_mut(), outlook.iter().find(|o| o.regressive)) {
+        text.push_str(&regressive_warning(o));
+    }
+
+    // 6. Формирование вывода
+    let (inv_label, level_label) = match primary {
+        Some(d) => (d.violated_invariant.label.as_str(), d.affected_level.name()),
+        None => ("Онтологический инвариант", "Unknown"),
+    };
+    let mut summary = format!(
+        "[NoemaFeed • {}]\nНарушение: {} ({})\nТип возмущения: {}\n{}",
         mode.label(),
-        diagnosis.violated_invariant,
+        inv_label,
         level_label,
-        pert_type,
-        temperament
+        perturbation_type,
+        personalization_line(temperament_hint(temperament))
     );
+    for d in violations.iter().skip(1) {
+        summary.push_str(&format!(
+            "\nТакже: {} ({}, {:.2})",
+            d.violated_invariant.label,
+            d.affected_level.name(),
+            d.severity
+        ));
+    }
+    match analogs.analogs.first() {
+        Some(a) => summary.push_str(&format!(
+            "\nАналог: {} ({}), сходство {:.2}",
+            a.label.as_deref().unwrap_or(a.event.local_name()),
+            a.occurred_at.as_deref().unwrap_or("дата неизвестна"),
+            a.similarity
+        )),
+        None => summary.push_str("\nКонтекст: потенциально уникальное событие — аналогов в индексе нет"),
+    }
+    for track in &phases {
+        summary.push_str(&phase_line(track, features.id.as_ref()));
+    }
+    for o in &outlook {
+        summary.push_str(&outlook_line(o));
+    }
+    // Периодичность — в критическом режиме: «цикл повторяется каждые N лет»
+    if mode == AnalysisMode::Critical {
+        for p in &cycles {
+            summary.push_str(&cycle_line(p));
+        }
+    }
+    for s in suggestions.iter().filter(|s| s.verdict != Verdict::Allowed) {
+        let frames: Vec<String> = s
+            .citations
+            .iter()
+            .map(|c| format!("{} ({})", c.label.as_deref().unwrap_or(c.frame.local_name()), c.frame))
+            .collect();
+        let verdict = if s.verdict == Verdict::Vetoed { "Отклонено" } else { "С оговоркой" };
+        summary.push_str(&format!(
+            "\n{}: {} — коллективная память: {}",
+            verdict,
+            s.label.as_deref().unwrap_or(s.action.local_name()),
+            frames.join(", ")
+        ));
+    }
+    let kb_problems = kb.manifest.problems();
+    if !kb.manifest.compatible {
+        summary.push_str(&format!("\nВнимание: KB неполна или несовместима с движком — {}", kb_problems.join("; ")));
+    }
 
     Ok(OntoScene {
         event_id: event.id.clone(),
         mode,
-        affected_level: diagnosis.affected_level,
-        violated_invariant: diagnosis.violated_invariant,
-        perturbation_type: pert_type,
-        temperament_hint: temperament,
+        violations,
+        perturbation_type,
+        temperament,
         reconstruction,
+        suggestions,
+        analogs,
+        cycles,
+        phases,
+        outlook,
         summary,
+        knowledge: kb.manifest.used(),
+        kb_problems,
+        trace,
     })
-}
\ No newline at end of file
+}
+
+// === Экземпляр движка ===
+
+/// Движок с собственным хранилищем KB, профилем и параметрами анализа.
+/// Несколько экземпляров независимы: у каждого своё хранилище и свой набор пакетов.
+pub struct NoemaEngine {
+    kb: Option<KnowledgeBase>, // None — движок освобождён (`dispose`)
+    sources: Vec<PackSource>,
+    events: Vec<EventRecord>, // события, добавленные во время работы; переживают перезагрузку KB
+    profile: Profile,
+    options: AnalysisOptions,
+}
+
+impl NoemaEngine {
+    /// Создаёт движок и загружает в него встроенные базы знаний
+    pub fn new(profile: Profile, options: AnalysisOptions) -> Result<Self, Box<dyn std::error::Error>> {
+        let sources = builtin_packs();
+        Ok(Self {
+            kb: Some(build_kb(&sources, &[])?),
+            sources,
+            events: Vec::new(),
+            profile,
+            options,
+        })
+    }
+
+    /// Загруженная KB; ошибка, если движок уже освобождён
+    pub fn kb(&self) -> Result<&KnowledgeBase, Box<dyn std::error::Error>> {
+        self.kb.as_ref().ok_or_else(|| "Движок освобождён: вызовите reload()".into())
+    }
+
+    pub fn store(&self) -> Result<&Store, Box<dyn std::error::Error>> {
+        Ok(&self.kb()?.store)
+    }
+
+    /// Инварианты загруженной KB
+    pub fn invariants(&self) -> Result<&InvariantRegistry, Box<dyn std::error::Error>> {
+        Ok(&self.kb()?.invariants)
+    }
+
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Смена профиля; `Profile::Neutral` — удаление профиля
+    pub fn set_profile(&mut self, profile: Profile) {
+        self.profile = profile;
+    }
+
+    pub fn options(&self) -> &AnalysisOptions {
+        &self.options
+    }
+
+    pub fn set_options(&mut self, options: AnalysisOptions) {
+        self.options = options;
+    }
+
+    /// Анализ события с профилем и параметрами движка
+    pub fn analyze(&self, event: &NewsEvent, mode: AnalysisMode) -> Result<OntoScene, Box<dyn std::error::Error>> {
+        build_onto_scene(self.kb()?, event, mode, &self.profile, &self.options)
+    }
+
+    /// Перезагружает KB в новое хранилище, включая пакеты, добавленные во время работы;
+    /// при ошибке прежнее хранилище сохраняется
+    pub fn reload(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.kb = Some(build_kb(&self.sources, &self.events)?);
+        Ok(())
+    }
+
+    /// Добавляет событие в контекстуальный индекс и заново выполняет вывод и правила KB.
+    /// Событие запоминается только после успешного вывода и перестройки индекса; при ошибке
+    /// его триплеты удаляются, а вывод пересчитывается по прежнему хранилищу.
+    pub fn add_event(&mut self, event: EventRecord) -> Result<&RuleReport, Box<dyn std::error::Error>> {
+        let kb = self.kb.as_mut().ok_or("Движок освобождён: вызовите reload()")?;
+        events::add_event(&kb.store, &event)?;
+        let indexed = rules::infer(&kb.store).and_then(|inferred| {
+            let index = context::index(&kb.store)?;
+            let cycles = cycles::load(&kb.store, &index)?;
+            Ok((inferred, index, cycles))
+        });
+        match indexed {
+            Ok(((entailment, rules), index, cycles)) => {
+                (kb.entailment, kb.rules, kb.index, kb.cycles) = (entailment, rules, index, cycles)
+            }
+            Err(e) => {
+                events::remove_event(&kb.store, &event.id)?;
+                (kb.entailment, kb.rules) = rules::infer(&kb.store)?;
+                return Err(e.into());
+            }
+        }
+        self.events.push(event);
+        Ok(&kb.rules)
+    }
+
+    /// Событие индекса, от которого строятся причинные фреймы: сама новость, если она
+    /// в индексе, иначе её ближайший аналог (`true`); `None` — событие потенциально уникально
+    fn frame_anchor(
+        &self,
+        event: &NewsEvent,
+        index: &BTreeMap<Iri, IndexedEvent>,
+    ) -> Result<Option<(Iri, bool)>, Box<dyn std::error::Error>> {
+        if let Some(id) = Iri::new(&event.id).ok().filter(|id| index.contains_key(id)) {
+            return Ok(Some((id, false)));
+        }
+        // Тип возмущения — как в анализе
+        let kb = self.kb()?;
+        let violations = inference::diagnose(&kb.triggers, &kb.invariants, &event.text(), self.options.severity_cutoff, None);
+        let perturbation = inference::event_perturbation(&kb.triggers, violations.first(), &event.title, None);
+        let report = analogs::find(&news_features(index, event, perturbation), index, 1, None);
+        Ok(report.analogs.into_iter().next().map(|analog| (analog.event, true)))
+    }
+
+    /// Фрейм следствий: цепочки `temporal:ledTo` вперёд от события индекса или его аналога
+    pub fn effect_frame(&self, event: &NewsEvent, max_depth: usize) -> Result<Option<EffectFrame>, Box<dyn std::error::Error>> {
+        let kb = self.kb()?;
+        let index = &kb.index;
+        let Some((anchor, via_analog)) = self.frame_anchor(event, index)? else {
+            return Ok(None);
+        };
+        Ok(Some(frames::effect_frame(&anchor, via_analog, index, &kb.invariants, max_depth)?))
+    }
+
+    /// Фрейм причин: триггеры, предшествующие события, циклы и предпосылки события индекса или его аналога
+    pub fn cause_frame(&self, event: &NewsEvent, max_depth: usize) -> Result<Option<CauseFrame>, Box<dyn std::error::Error>> {
+        let kb = self.kb()?;
+        let index = &kb.index;
+        let Some((anchor, via_analog)) = self.frame_anchor(event, index)? else {
+            return Ok(None);
+        };
+        Ok(Some(frames::cause_frame(&kb.store, &anchor, via_analog, index, &kb.cycles, max_depth)?))
+    }
+
+    /// Текущая фаза онтогенеза темы (актора или цикла) и история переходов по событиям индекса
+    pub fn phase(&self, subject: &Iri) -> Result<PhaseTrack, Box<dyn std::error::Error>> {
+        let kb = self.kb()?;
+        Ok(ontogenesis::track(&kb.store, &kb.phases, &kb.index, subject, None, None)?)
+    }
+
+    /// Манифест KB: версии пакетов, совместимость, недостающие обязательные базы
+    pub fn manifest(&self) -> Result<KbManifest, Box<dyn std::error::Error>> {
+        Ok(self.kb()?.manifest.clone())
+    }
+
+    /// SPARQL-запрос к KB только на чтение (SELECT, ASK, CONSTRUCT)
+    pub fn query(&self, sparql: &str, limits: &QueryLimits) -> Result<QueryOutput, Box<dyn std::error::Error>> {
+        Ok(query::query(self.store()?, sparql, limits)?)
+    }
+
+    /// Проверка загруженной KB правилами формы (см. `knowledge::validate`)
+    pub fn validate(&self) -> Result<Vec<ShapeViolation>, Box<dyn std::error::Error>> {
+        Ok(validate::validate(self.store()?)?)
+    }
+
+    /// Загруженные пакеты KB
+    pub fn packs(&self) -> Result<Vec<PackInfo>, Box<dyn std::error::Error>> {
+        Ok(packs::list_packs(self.store()?)?)
+    }
+
+    /// Добавляет пакет KB (Turtle) в собственный именованный граф
+    pub fn load_pack(&mut self, name: &str, data: &[u8]) -> Result<PackInfo, Box<dyn std::error::Error>> {
+        if self.sources.iter().any(|(n, _)| n == name) {
+            return Err(format!("Пакет {} уже загружен", name).into());
+        }
+        let mut sources = self.sources.clone();
+        sources.push((name.to_string(), Cow::Owned(data.to_vec())));
+        self.apply(sources, name)
+    }
+
+    /// Заменяет содержимое загруженного пакета, в том числе встроенного
+    pub fn replace_pack(&mut self, name: &str, data: &[u8]) -> Result<PackInfo, Box<dyn std::error::Error>> {
+        let mut sources = self.sources.clone();
+        let source = sources
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| format!("Пакет {} не загружен", name))?;
+        source.1 = Cow::Owned(data.to_vec());
+        self.apply(sources, name)
+    }
+
+    /// Выгружает пакет; `false` — такого пакета не было
+    pub fn unload_pack(&mut self, name: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut sources = self.sources.clone();
+        let before = sources.len();
+        sources.retain(|(n, _)| n != name);
+        if sources.len() == before {
+            return Ok(false);
+        }
+        self.kb = Some(build_kb(&sources, &self.events)?);
+        self.sources = sources;
+        Ok(true)
+    }
+
+    /// Собирает хранилище из нового набора пакетов и подменяет текущее только при успехе:
+    /// пакет, ломающий согласованность KB, не оставляет движок в промежуточном состоянии
+    fn apply(&mut self, sources: Vec<PackSource>, name: &str) -> Result<PackInfo, Box<dyn std::error::Error>> {
+        let kb = build_kb(&sources, &self.events)?;
+        let info = packs::list_packs(&kb.store)?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Пакет {} не найден после загрузки", name))?;
+        self.kb = Some(kb);
+        self.sources = sources;
+        Ok(info)
+    }
+
+    /// Освобождает хранилище; профиль и параметры сохраняются до `reload`
+    pub fn dispose(&mut self) {
+        self.kb = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::reasoner;
+    use oxigraph::model::vocab::rdf;
+    use oxigraph::model::{NamedNode, Quad};
+
+    fn engine() -> NoemaEngine {
+        NoemaEngine::new(Profile::Neutral, AnalysisOptions::default()).unwrap()
+    }
+
+    fn news(title: &str) -> NewsEvent {
+        NewsEvent {
+            id: String::new(),
+            title: title.to_string(),
+            content: None,
+            source_url: None,
+            published_at: None,
+            actors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn trace_is_recorded_only_when_requested() {
+        let mut engine = engine();
+        let event = news("Непрозрачный алгоритм решает за наблюдателя");
+        assert!(engine.analyze(&event, AnalysisMode::Critical).unwrap().trace.is_none());
+
+        engine.set_options(AnalysisOptions {
+            explain: true,
+            ..Default::default()
+        });
+        let trace = engine.analyze(&event, AnalysisMode::Critical).unwrap().trace.unwrap();
+        assert!(trace.matched_terms.iter().any(|m| m.resource == "fus:ObserverPosition"));
+        assert!(trace.kb_triples.iter().any(|t| t.contains("fus:belongsToLevel")));
+        assert!(!trace.scoring.is_empty());
+    }
+
+    #[test]
+    fn neutral_profile_is_not_personalized() {
+        let event = news("Непрозрачный алгоритм решает за наблюдателя");
+        let scene = engine().analyze(&event, AnalysisMode::Critical).unwrap();
+        assert_eq!(scene.temperament, None);
+        assert_eq!(temperament_hint(scene.temperament), NEUTRAL_HINT);
+        assert!(scene.summary.contains("Персонализация: не применялась (профиль не задан)"));
+        assert!(!scene.summary.contains("Для темперамента"));
+        assert_eq!(
+            scene.reconstruction.as_deref(),
+            Some("Обеспечить прозрачность алгоритма и право на объяснение решения.")
+        );
+    }
+
+    #[test]
+    fn disposed_engine_refuses_analysis_until_reload() {
+        let mut engine = engine();
+        let event = news("Непрозрачный алгоритм решает за наблюдателя");
+        engine.dispose();
+        assert!(engine.analyze(&event, AnalysisMode::Critical).is_err());
+        engine.reload().unwrap();
+        assert!(engine.analyze(&event, AnalysisMode::Critical).is_ok());
+    }
+
+    fn ex(name: &str) -> NamedNode {
+        NamedNode::new_unchecked(format!("http://example.org/{name}"))
+    }
+
+    /// Тип `ex:Rex` в графе: пакета или выведенном
+    fn rex_is(store: &Store, class: &str, graph: &NamedNode) -> bool {
+        let quad = Quad::new(ex("Rex"), rdf::TYPE, ex(class), graph.clone());
+        store.contains(&quad).unwrap()
+    }
+
+    #[test]
+    fn replacing_and_unloading_a_pack_rebuilds_the_kb() {
+        let mut engine = engine();
+        let pack = packs::graph_iri("test/pets.ttl").unwrap();
+        let inferred = reasoner::inferred_graph();
+        let prefixes = "@prefix ex: <http://example.org/> .\n@prefix fus: <http://onto16.org/fus/> .\n\
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n";
+        let violated = |engine: &NoemaEngine| {
+            let scene = engine.analyze(&news("Кот ушёл"), AnalysisMode::Critical).unwrap();
+            scene.primary().map(|d| d.violated_invariant.id.curie())
+        };
+        assert_eq!(violated(&engine), None);
+
+        let info = engine
+            .load_pack("test/pets.ttl", format!("{prefixes}ex:Rex a ex:Dog . ex:Dog rdfs:subClassOf ex:Animal . fus:Distinction fus:triggerTerm \"кот\"@ru .").as_bytes())
+            .unwrap();
+        assert_eq!((info.graph.as_str(), info.triples), (pack.as_str(), 3));
+        assert!(rex_is(engine.store().unwrap(), "Dog", &pack));
+        assert!(rex_is(engine.store().unwrap(), "Animal", &inferred));
+        // Триггеры пакета действуют сразу после загрузки
+        assert_eq!(violated(&engine).as_deref(), Some("fus:Distinction"));
+
+        let info = engine
+            .replace_pack("test/pets.ttl", format!("{prefixes}ex:Rex a ex:Cat . ex:Cat rdfs:subClassOf ex:Pet .").as_bytes())
+            .unwrap();
+        assert_eq!(info.triples, 2);
+        let store = engine.store().unwrap();
+        assert!(!rex_is(store, "Dog", &pack));
+        assert!(rex_is(store, "Cat", &pack));
+        assert!(!rex_is(store, "Animal", &inferred));
+        assert!(rex_is(store, "Pet", &inferred));
+        assert_eq!(violated(&engine), None);
+
+        assert!(engine.unload_pack("test/pets.ttl").unwrap());
+        assert!(!engine.unload_pack("test/pets.ttl").unwrap());
+        let store = engine.store().unwrap();
+        assert!(!store.contains_named_graph(&pack).unwrap());
+        assert!(!rex_is(store, "Pet", &inferred));
+        assert!(engine.load_pack("fus/core.ttl", b"").is_err());
+    }
+
+    #[test]
+    fn regressive_branch_is_named_by_label_or_iri() {
+        let mut outlook = TransitionOutlook {
+            from: Iri::from_curie("onto:Crisis"),
+            from_label: Some("Кризис".to_string()),
+            temperament: Iri::from_curie("temper:Choleric"),
+            temperament_label: Some("Холерик".to_string()),
+            transition: Some(Iri::from_curie("onto:CrisisToControl")),
+            to: Some(Iri::from_curie("onto:Control")),
+            to_label: Some("Контроль".to_string()),
+            regressive: true,
+        };
+        assert!(outlook_line(&outlook).ends_with("вероятен переход в «Контроль» — регрессивная ветвь"));
+        assert!(regressive_warning(&outlook).starts_with(" Осторожно: для темперамента «Холерик» типичен выход из кризиса через «Контроль»"));
+
+        outlook.to_label = None;
+        assert!(regressive_warning(&outlook).contains("через «Control»"));
+        assert!(outlook_line(&outlook).contains("переход в «Control»"));
+    }
+}
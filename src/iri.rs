@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Канонические IRI ресурсов KB: реестр префиксов (CURIE ↔ полный IRI)
+// и тип `Iri`, чтобы код не передавал строки в неизвестной записи
+
+use std::fmt;
+
+use oxigraph::model::{NamedNode, NamedNodeRef, Term};
+use oxigraph::store::Store;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Реестр префиксов: пространства имён, объявленные в kb/*.ttl
+pub const PREFIXES: &[(&str, &str)] = &[
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("dct", "http://purl.org/dc/terms/"),
+    ("fus", "http://onto16.org/fus/"),
+    ("zodiac", "http://onto16.org/fus/zodiac/"),
+    ("chinese", "http://onto16.org/fus/chinese/"),
+    ("temper", "http://onto16.org/fus/temperament/"),
+    ("pert", "http://onto16.org/pert/"),
+    ("temporal", "http://onto16.org/temporal/"),
+    ("onto", "http://onto16.org/ontogeny/"),
+    ("rule", "http://onto16.org/rule/"),
+    ("memory", "http://onto16.org/memory/"),
+    ("wd", "http://www.wikidata.org/entity/"),
+];
+
+/// Устаревшие пространства имён → канонические.
+/// Ранние версии ядра строили `http://onto16.org/fus#…`, тогда как KB объявляет `fus/`.
+const LEGACY_NAMESPACES: &[(&str, &str)] = &[("http://onto16.org/fus#", "http://onto16.org/fus/")];
+
+/// Полный IRI ресурса в канонической записи.
+/// Отображается и сериализуется как CURIE (`fus:ObserverPosition`), если префикс известен.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Iri(String);
+
+impl Iri {
+    /// IRI из полной записи или CURIE; незарегистрированный префикс — ошибка
+    pub fn new(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let full = if is_absolute(value) {
+            canonical(value)
+        } else {
+            let (prefix, local) = value
+                .split_once(':')
+                .ok_or_else(|| format!("{}: ожидался IRI или CURIE", value))?;
+            let (_, ns) = PREFIXES
+                .iter()
+                .find(|(p, _)| *p == prefix)
+                .ok_or_else(|| format!("{}: неизвестный префикс {}:", value, prefix))?;
+            format!("{}{}", ns, local)
+        };
+        NamedNode::new(full.as_str()).map_err(|e| format!("{}: {}", value, e))?;
+        Ok(Self(full))
+    }
+
+    /// IRI из CURIE, записанного в коде; незарегистрированный префикс — ошибка программы
+    pub fn from_curie(curie: &str) -> Self {
+        Self::new(curie).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Компактная запись: `fus:ObserverPosition`; без известного префикса — полный IRI
+    pub fn curie(&self) -> String {
+        compact(&self.0)
+    }
+
+    /// Локальное имя: `fus:OntologicalLevel` → `OntologicalLevel`
+    pub fn local_name(&self) -> &str {
+        self.0.rsplit(['/', '#']).next().unwrap_or(&self.0)
+    }
+
+    pub fn as_node(&self) -> NamedNodeRef<'_> {
+        NamedNodeRef::new_unchecked(&self.0)
+    }
+
+    /// Терм для подстановки в SPARQL-запрос
+    pub fn term(&self) -> Term {
+        self.as_node().into_owned().into()
+    }
+}
+
+impl fmt::Display for Iri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.curie())
+    }
+}
+
+impl From<NamedNode> for Iri {
+    fn from(node: NamedNode) -> Self {
+        Self(canonical(node.as_str()))
+    }
+}
+
+impl Serialize for Iri {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.curie())
+    }
+}
+
+impl<'de> Deserialize<'de> for Iri {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Iri::new(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Абсолютный IRI (со схемой), а не CURIE
+fn is_absolute(value: &str) -> bool {
+    value.contains("://") || value.starts_with("urn:")
+}
+
+/// Приводит устаревшее пространство имён к каноническому
+fn canonical(iri: &str) -> String {
+    for (legacy, ns) in LEGACY_NAMESPACES {
+        if let Some(local) = iri.strip_prefix(legacy) {
+            return format!("{}{}", ns, local);
+        }
+    }
+    iri.to_string()
+}
+
+/// Раскрывает CURIE в полный IRI; строку с неизвестным префиксом возвращает как есть
+pub fn expand(curie: &str) -> String {
+    Iri::new(curie).map(|iri| iri.0).unwrap_or_else(|_| curie.to_string())
+}
+
+/// Сворачивает полный IRI в CURIE по самому длинному подходящему пространству имён
+pub fn compact(iri: &str) -> String {
+    let iri = canonical(iri);
+    PREFIXES
+        .iter()
+        .filter(|(_, ns)| iri.starts_with(ns))
+        .max_by_key(|(_, ns)| ns.len())
+        .map(|(prefix, ns)| format!("{}:{}", prefix, &iri[ns.len()..]))
+        .unwrap_or(iri)
+}
+
+// === Проверка по хранилищу ===
+
+/// Встречается ли IRI в хранилище — субъектом, предикатом или объектом
+pub fn exists(store: &Store, iri: &Iri) -> Result<bool, String> {
+    let node = iri.as_node();
+    let found = |quad: Option<Result<_, _>>| -> Result<bool, String> {
+        quad.transpose().map(|q| q.is_some()).map_err(|e: oxigraph::store::StorageError| e.to_string())
+    };
+    Ok(found(store.quads_for_pattern(Some(node.into()), None, None, None).next())?
+        || found(store.quads_for_pattern(None, Some(node), None, None).next())?
+        || found(store.quads_for_pattern(None, None, Some(node.into()), None).next())?)
+}
+
+/// IRI из списка, которых нет в хранилище: ссылки кода на несуществующие ресурсы
+pub fn missing(store: &Store, iris: &[Iri]) -> Result<Vec<Iri>, String> {
+    let mut missing = Vec::new();
+    for iri in iris {
+        if !exists(store, iri)? {
+            missing.push(iri.clone());
+        }
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_and_compact() {
+        assert_eq!(expand("fus:ObserverPosition"), "http://onto16.org/fus/ObserverPosition");
+        assert_eq!(expand("nope:Thing"), "nope:Thing");
+        assert_eq!(compact("http://onto16.org/pert/Leak"), "pert:Leak");
+        assert_eq!(compact("http://example.org/Thing"), "http://example.org/Thing");
+        // Самое длинное пространство имён: zodiac:, а не fus:
+        assert_eq!(compact("http://onto16.org/fus/zodiac/Aries"), "zodiac:Aries");
+        assert_eq!(Iri::from_curie("temper:Choleric").curie(), "temper:Choleric");
+        assert_eq!(Iri::from_curie("temper:Choleric").local_name(), "Choleric");
+        assert!(Iri::new("nope:Thing").is_err());
+        assert!(Iri::new("Thing").is_err());
+    }
+
+    #[test]
+    fn every_prefix_round_trips() {
+        for (prefix, ns) in PREFIXES {
+            let curie = format!("{}:Local", prefix);
+            let iri = Iri::new(&curie).unwrap();
+            assert_eq!(iri.as_str(), format!("{}Local", ns));
+            assert_eq!(iri.curie(), curie);
+            assert_eq!(Iri::new(iri.as_str()).unwrap(), iri);
+        }
+    }
+
+    #[test]
+    fn legacy_fus_namespace_is_canonicalized() {
+        let legacy = Iri::new("http://onto16.org/fus#ObserverPosition").unwrap();
+        assert_eq!(legacy, Iri::from_curie("fus:ObserverPosition"));
+        assert_eq!(legacy.as_str(), "http://onto16.org/fus/ObserverPosition");
+        assert_eq!(compact("http://onto16.org/fus#ObserverPosition"), "fus:ObserverPosition");
+        let node = NamedNode::new_unchecked("http://onto16.org/fus#ObserverPosition");
+        assert_eq!(Iri::from(node), legacy);
+    }
+}
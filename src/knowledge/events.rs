@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// События, добавленные во время работы: записи контекстуального индекса
+// (`temporal:Event`) в отдельном графе, вне пакетов KB
+
+use oxigraph::model::vocab::{rdf, rdfs, xsd};
+use oxigraph::model::{Literal, NamedNode, Quad, Term};
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+
+use crate::core::astro::days_in_month;
+use crate::iri::Iri;
+
+/// Граф событий, добавленных во время работы
+pub const EVENTS_GRAPH: &str = "http://onto16.org/events";
+
+/// Событие контекстуального индекса (JSON: IRI в записи CURIE или полной)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventRecord {
+    pub id: Iri,
+    pub label: String,
+    #[serde(default)]
+    pub occurred_at: Option<String>, // xsd:date: "2026-02-01"
+    #[serde(default)]
+    pub actors: Vec<Iri>, // temporal:involvedActor
+    #[serde(default)]
+    pub actions: Vec<Iri>, // temporal:describesAction: тип возмущения или триггер
+    #[serde(default)]
+    pub triggered_by: Vec<Iri>, // temporal:triggeredBy: предшествующие события
+    #[serde(default)]
+    pub cycles: Vec<Iri>, // temporal:partOfCycle: циклы temporal:Cycle
+}
+
+/// Дата `ГГГГ-ММ-ДД` → (год, месяц, день). Берутся первые 10 символов: время и часовой
+/// пояс RFC 3339 (`published_at` вида `2026-02-10T12:00:00Z`) отбрасываются.
+pub fn parse_date(value: &str) -> Option<(i32, u32, u32)> {
+    let value = value.trim();
+    let mut parts = value.get(..10).unwrap_or(value).splitn(3, '-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)).then_some((year, month, day))
+}
+
+fn temporal(local: &str) -> NamedNode {
+    Iri::from_curie(&format!("temporal:{}", local)).as_node().into_owned()
+}
+
+/// Триплеты события в графе `EVENTS_GRAPH`
+fn quads(event: &EventRecord) -> Result<Vec<Quad>, String> {
+    let graph = NamedNode::new_unchecked(EVENTS_GRAPH);
+    let subject = event.id.as_node().into_owned();
+    let quad = |predicate: NamedNode, object: Term| {
+        Quad::new(subject.clone(), predicate, object, graph.clone())
+    };
+
+    let mut quads = vec![
+        quad(rdf::TYPE.into_owned(), temporal("Event").into()),
+        quad(rdfs::LABEL.into_owned(), Literal::new_simple_literal(&event.label).into()),
+    ];
+    if let Some(date) = &event.occurred_at {
+        let (year, month, day) =
+            parse_date(date).ok_or_else(|| format!("{}: ожидалась дата ГГГГ-ММ-ДД, получено: {}", event.id, date))?;
+        let date = format!("{:04}-{:02}-{:02}", year, month, day);
+        quads.push(quad(temporal("occurredAt"), Literal::new_typed_literal(date, xsd::DATE).into()));
+    }
+    for (property, targets) in [
+        ("involvedActor", &event.actors),
+        ("describesAction", &event.actions),
+        ("triggeredBy", &event.triggered_by),
+        ("partOfCycle", &event.cycles),
+    ] {
+        for target in targets {
+            quads.push(quad(temporal(property), target.term()));
+        }
+    }
+    Ok(quads)
+}
+
+/// Добавляет событие в граф `EVENTS_GRAPH`. IRI, который уже описан в KB
+/// (событие пакета или добавленное ранее), — ошибка: триплеты не сливаются с чужим ресурсом.
+pub fn add_event(store: &Store, event: &EventRecord) -> Result<(), String> {
+    let graph = NamedNode::new_unchecked(EVENTS_GRAPH);
+    let exists = store
+        .quads_for_pattern(Some(event.id.as_node().into()), None, None, None)
+        .next()
+        .is_some();
+    if exists {
+        return Err(format!("Ресурс {} уже есть в KB", event.id));
+    }
+    let quads = quads(event)?;
+    store.insert_named_graph(&graph).map_err(|e| e.to_string())?;
+    store.extend(quads).map_err(|e| e.to_string())
+}
+
+/// Удаляет триплеты события из графа `EVENTS_GRAPH` (откат `add_event`)
+pub fn remove_event(store: &Store, id: &Iri) -> Result<(), String> {
+    let graph = NamedNode::new_unchecked(EVENTS_GRAPH);
+    let quads: Vec<Quad> = store
+        .quads_for_pattern(Some(id.as_node().into()), None, None, Some(graph.as_ref().into()))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    for quad in &quads {
+        store.remove(quad).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
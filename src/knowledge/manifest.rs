@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Манифест KB: метаданные пакетов из заголовка `<>` (owl:versionInfo, dct:created,
+// dct:license), проверка версий и полноты «10 баз» из KB_SPEC.md
+
+use std::collections::BTreeMap;
+
+use oxigraph::store::Store;
+use serde::Serialize;
+
+use crate::knowledge::packs;
+use crate::sparql;
+
+/// Старшая версия формата KB, которую понимает движок
+pub const SUPPORTED_MAJOR: u32 = 1;
+
+/// Обязательные пакеты («10 баз») и минимальная версия, на которую рассчитан движок:
+/// - astro-profiles 1.1.0 — словарь знаков и календарь;
+/// - subject-registry 1.0.1 — исправленный синтаксис Turtle;
+/// - perturbations 1.1.0 — действия реконструкции `pert:ResponseAction`;
+/// - ontogenesis 1.2.0 — переходы `onto:Transition` с предпочтениями темпераментов;
+/// - context-index 1.2.0 — циклы как ресурсы `temporal:Cycle`.
+pub const REQUIRED_PACKS: &[(&str, &str)] = &[
+    // === Ядро ФУС ===
+    ("fus/core.ttl", "1.0.0"),
+    ("fus/structural.ttl", "1.0.0"),
+    ("fus/archetypal.ttl", "1.0.0"),
+    ("fus/identity.ttl", "1.0.0"),
+    ("fus/existential.ttl", "1.0.0"),
+    // === Профили субъектов ===
+    ("subjects/astro-profiles.ttl", "1.1.0"),
+    ("subjects/subject-registry.ttl", "1.0.1"),
+    // === Динамические библиотеки ===
+    ("dynamics/perturbations.ttl", "1.1.0"),
+    ("dynamics/ontogenesis.ttl", "1.2.0"),
+    ("dynamics/context-index.ttl", "1.2.0"),
+];
+
+/// Метаданные одного пакета
+#[derive(Serialize, Debug, Clone)]
+pub struct PackManifest {
+    pub name: String,
+    pub graph: String,
+    pub title: Option<String>,
+    pub version: Option<String>, // owl:versionInfo
+    pub created: Option<String>, // dct:created
+    pub license: Option<String>, // dct:license
+    pub triples: usize,
+    pub required: bool,
+    pub issues: Vec<String>, // несовместимость версии, пропущенные поля заголовка
+}
+
+/// Манифест загруженной KB
+#[derive(Serialize, Debug, Clone)]
+pub struct KbManifest {
+    pub packs: Vec<PackManifest>,
+    pub missing: Vec<String>, // обязательные пакеты, которых нет в хранилище
+    pub compatible: bool,     // все обязательные пакеты на месте и подходящих версий
+}
+
+impl KbManifest {
+    /// Использованные знания: «имя@версия» для каждого пакета
+    pub fn used(&self) -> Vec<String> {
+        self.packs
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version.as_deref().unwrap_or("?")))
+            .collect()
+    }
+
+    /// Все проблемы одной строкой на каждую: отсутствующие и несовместимые пакеты
+    pub fn problems(&self) -> Vec<String> {
+        let missing = self.missing.iter().map(|name| format!("{}: обязательный пакет не загружен", name));
+        let issues = self
+            .packs
+            .iter()
+            .flat_map(|p| p.issues.iter().map(move |issue| format!("{}: {}", p.name, issue)));
+        missing.chain(issues).collect()
+    }
+}
+
+/// Заголовки всех пакетов: `<>` раскрывается в IRI графа пакета
+const HEADER_QUERY: &str = r#"
+SELECT ?graph ?title ?version ?created ?license WHERE {
+    GRAPH ?graph {
+        { ?graph dct:title ?title }
+        UNION { ?graph owl:versionInfo ?version }
+        UNION { ?graph dct:created ?created }
+        UNION { ?graph dct:license ?license }
+    }
+}
+"#;
+
+/// Версия «X.Y.Z»; недостающие части — нули
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Проблемы версии пакета относительно требуемой движком
+fn version_issue(version: Option<&str>, required: Option<&str>) -> Option<String> {
+    let Some(version) = version else {
+        return Some("нет owl:versionInfo".to_string());
+    };
+    let Some(parsed) = parse_version(version) else {
+        return Some(format!("неразборчивая версия {}", version));
+    };
+    if parsed.0 != SUPPORTED_MAJOR {
+        return Some(format!("версия {} не поддерживается (движок понимает {}.x)", version, SUPPORTED_MAJOR));
+    }
+    let required = required.and_then(parse_version)?;
+    if parsed < required {
+        return Some(format!(
+            "версия {} ниже требуемой {}.{}.{}",
+            version, required.0, required.1, required.2
+        ));
+    }
+    None
+}
+
+/// Собирает манифест по пакетам хранилища
+pub fn build(store: &Store) -> Result<KbManifest, String> {
+    let mut headers: BTreeMap<String, [Option<String>; 4]> = BTreeMap::new();
+    for row in sparql::select(store, HEADER_QUERY, &[])? {
+        let Some(graph) = sparql::iri(&row, "graph") else {
+            continue;
+        };
+        let header = headers.entry(graph.as_str().to_string()).or_default();
+        for (i, var) in ["title", "version", "created", "license"].iter().enumerate() {
+            let value = sparql::literal(&row, var).or_else(|| sparql::iri(&row, var).map(|iri| iri.as_str().to_string()));
+            if header[i].is_none() {
+                header[i] = value;
+            }
+        }
+    }
+
+    let mut packs = Vec::new();
+    for pack in packs::list_packs(store)? {
+        let [title, version, created, license] = headers.remove(&pack.graph).unwrap_or_default();
+        let required = REQUIRED_PACKS.iter().find(|(name, _)| *name == pack.name).map(|(_, v)| *v);
+
+        let mut issues = Vec::new();
+        issues.extend(version_issue(version.as_deref(), required));
+        if created.is_none() {
+            issues.push("нет dct:created".to_string());
+        }
+        if license.is_none() {
+            issues.push("нет dct:license".to_string());
+        }
+
+        packs.push(PackManifest {
+            name: pack.name,
+            graph: pack.graph,
+            title,
+            version,
+            created,
+            license,
+            triples: pack.triples,
+            required: required.is_some(),
+            issues,
+        });
+    }
+
+    let missing: Vec<String> = REQUIRED_PACKS
+        .iter()
+        .filter(|(name, _)| !packs.iter().any(|p| p.name == *name))
+        .map(|(name, _)| name.to_string())
+        .collect();
+    let compatible = missing.is_empty() && packs.iter().filter(|p| p.required).all(|p| p.issues.is_empty());
+
+    Ok(KbManifest {
+        packs,
+        missing,
+        compatible,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_issue_checks_major_and_minimum() {
+        assert_eq!(version_issue(Some("1.2.0"), Some("1.2.0")), None);
+        assert_eq!(version_issue(Some("1.3"), Some("1.2.1")), None);
+        assert_eq!(version_issue(Some("1.9.9"), None), None);
+        assert_eq!(version_issue(None, Some("1.0.0")).as_deref(), Some("нет owl:versionInfo"));
+        assert_eq!(version_issue(Some("1.x"), None).as_deref(), Some("неразборчивая версия 1.x"));
+        assert_eq!(
+            version_issue(Some("2.0.0"), Some("1.0.0")).as_deref(),
+            Some("версия 2.0.0 не поддерживается (движок понимает 1.x)")
+        );
+        assert_eq!(
+            version_issue(Some("1.1.9"), Some("1.2.0")).as_deref(),
+            Some("версия 1.1.9 ниже требуемой 1.2.0")
+        );
+    }
+
+    #[test]
+    fn outdated_and_missing_packs_make_kb_incompatible() {
+        let store = Store::new().unwrap();
+        // Все обязательные пакеты с заголовком, кроме fus/core.ttl; онтогенез устарел
+        let header = |version: &str| {
+            format!(
+                r#"@prefix owl: <http://www.w3.org/2002/07/owl#> .
+                @prefix dct: <http://purl.org/dc/terms/> .
+                <> owl:versionInfo "{version}" ; dct:created "2026-01-01" ; dct:license <https://spdx.org/licenses/MPL-2.0> ."#
+            )
+        };
+        for (name, version) in REQUIRED_PACKS {
+            let version = if *name == "dynamics/ontogenesis.ttl" { "1.1.0" } else { version };
+            if *name != "fus/core.ttl" {
+                packs::load_pack(&store, name, header(version).as_bytes()).unwrap();
+            }
+        }
+        let manifest = build(&store).unwrap();
+        assert!(!manifest.compatible);
+        assert_eq!(manifest.missing, ["fus/core.ttl"]);
+        assert_eq!(
+            manifest.problems(),
+            [
+                "fus/core.ttl: обязательный пакет не загружен",
+                "dynamics/ontogenesis.ttl: версия 1.1.0 ниже требуемой 1.2.0"
+            ]
+        );
+    }
+}
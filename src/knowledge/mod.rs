@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Базы знаний как данные: пакеты Turtle в именованных графах хранилища
+
+pub mod events;
+pub mod manifest;
+pub mod packs;
+pub mod query;
+pub mod reasoner;
+pub mod rules;
+pub mod validate;
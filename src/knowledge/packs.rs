@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Пакеты KB: каждый Turtle-файл загружается в собственный именованный граф,
+// поэтому его можно перечислить, выгрузить или заменить без пересборки WASM.
+// Замену и выгрузку выполняет `NoemaEngine`: он собирает хранилище заново,
+// чтобы вывод и кэши KB не хранили следов прежнего пакета
+
+use oxigraph::io::{RdfFormat, RdfParser};
+use oxigraph::model::{NamedNode, NamedOrBlankNode, Quad};
+use oxigraph::store::Store;
+use serde::Serialize;
+
+/// Базовый IRI баз знаний: граф пакета и `<>` в его заголовке — этот IRI плюс имя пакета
+pub const KB_BASE_IRI: &str = "http://onto16.org/kb/";
+
+/// Загруженный пакет
+#[derive(Serialize, Debug, Clone)]
+pub struct PackInfo {
+    pub name: String,  // путь относительно kb/, например: "fus/core.ttl"
+    pub graph: String, // IRI именованного графа
+    pub triples: usize,
+}
+
+/// Именованный граф пакета: `http://onto16.org/kb/fus/core.ttl`
+pub fn graph_iri(name: &str) -> Result<NamedNode, String> {
+    let name = name.trim().trim_start_matches('/');
+    if name.is_empty() {
+        return Err("Пустое имя пакета".to_string());
+    }
+    NamedNode::new(format!("{}{}", KB_BASE_IRI, name)).map_err(|e| format!("{}: {}", name, e))
+}
+
+/// Имя пакета по IRI графа; `None` — граф не из пространства KB
+pub fn pack_name(graph: &str) -> Option<&str> {
+    graph.strip_prefix(KB_BASE_IRI)
+}
+
+/// Разбирает Turtle целиком до записи в хранилище: ошибка синтаксиса
+/// не оставляет полузагруженный граф
+fn parse(graph: &NamedNode, name: &str, data: &[u8]) -> Result<Vec<Quad>, String> {
+    RdfParser::from_format(RdfFormat::Turtle)
+        .with_base_iri(graph.as_str())
+        .map_err(|e| e.to_string())?
+        .without_named_graphs()
+        .with_default_graph(graph.clone())
+        .rename_blank_nodes()
+        .for_slice(data)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("{}: {}", name, e))
+}
+
+/// Загружает пакет в новый именованный граф; повторная загрузка того же имени — ошибка
+pub fn load_pack(store: &Store, name: &str, data: &[u8]) -> Result<PackInfo, String> {
+    let graph = graph_iri(name)?;
+    if store.contains_named_graph(&graph).map_err(|e| e.to_string())? {
+        return Err(format!("Пакет {} уже загружен", name));
+    }
+    let quads = parse(&graph, name, data)?;
+    let triples = quads.len();
+    store.insert_named_graph(&graph).map_err(|e| e.to_string())?;
+    store.extend(quads).map_err(|e| e.to_string())?;
+    Ok(PackInfo {
+        name: name.to_string(),
+        graph: graph.into_string(),
+        triples,
+    })
+}
+
+/// Все пакеты хранилища, по имени
+pub fn list_packs(store: &Store) -> Result<Vec<PackInfo>, String> {
+    let mut packs = Vec::new();
+    for graph in store.named_graphs() {
+        let NamedOrBlankNode::NamedNode(graph) = graph.map_err(|e| e.to_string())? else {
+            continue;
+        };
+        let Some(name) = pack_name(graph.as_str()) else {
+            continue;
+        };
+        let triples = store
+            .quads_for_pattern(None, None, None, Some(graph.as_ref().into()))
+            .count();
+        packs.push(PackInfo {
+            name: name.to_string(),
+            graph: graph.as_str().to_string(),
+            triples,
+        });
+    }
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packs)
+}
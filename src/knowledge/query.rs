@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Запросы к KB только на чтение: SELECT и ASK — в SPARQL JSON, CONSTRUCT и DESCRIBE — в Turtle.
+// UPDATE отклоняется; размер результата и время выполнения ограничены.
+
+use std::error::Error;
+use std::fmt;
+
+use oxigraph::io::{RdfFormat, RdfSerializer};
+use oxigraph::model::{GraphName, GraphNameRef, NamedOrBlankNodeRef, Quad, Term, TermRef};
+use oxigraph::sparql::results::{QueryResultsFormat, QueryResultsSerializer};
+use oxigraph::sparql::{QueryEvaluationError, QueryResults};
+use oxigraph::store::Store;
+use serde::{Deserialize, Serialize};
+use spareval::{InternalQuad, QueryableDataset};
+
+use crate::iri::PREFIXES;
+use crate::sparql;
+
+/// Ограничения запроса (JSON, все поля необязательны)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct QueryLimits {
+    pub max_results: usize, // решений SELECT или триплетов CONSTRUCT
+    pub time_budget_ms: u64, // проверяется при каждом чтении из хранилища и на каждом решении
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            max_results: 1000,
+            time_budget_ms: 2000,
+        }
+    }
+}
+
+/// Вид запроса
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryKind {
+    Select,
+    Ask,
+    Graph, // CONSTRUCT или DESCRIBE
+}
+
+/// Результат запроса
+#[derive(Serialize, Debug, Clone)]
+pub struct QueryOutput {
+    pub kind: QueryKind,
+    pub format: &'static str, // "json" (SPARQL 1.1 Query Results JSON) или "turtle"
+    pub result: String,
+    pub count: usize,    // решений или триплетов в результате
+    pub truncated: bool, // результат обрезан по `max_results`
+}
+
+/// Время в миллисекундах: `Instant` и `SystemTime` в браузере паникуют, там — `Date.now()`
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
+}
+
+/// Ошибка чтения хранилища во время вычисления: превышен бюджет или сбой хранилища
+#[derive(Debug)]
+struct DatasetError(String);
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for DatasetError {}
+
+/// Бюджет времени запроса. Таймера в браузере нет (второго потока для отмены тоже),
+/// поэтому срок проверяется синхронно: на каждом чтении вычислителя из хранилища
+/// (см. `BudgetedStore`) и на каждом решении результата
+struct Deadline {
+    started: f64,
+    budget_ms: u64,
+}
+
+impl Deadline {
+    fn start(budget_ms: u64) -> Self {
+        Self {
+            started: now_ms(),
+            budget_ms,
+        }
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if now_ms() - self.started >= self.budget_ms as f64 {
+            Err(format!("Запрос превысил бюджет времени {} мс", self.budget_ms))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Хранилище для вычислителя SPARQL с проверкой срока на каждом чтении:
+/// сортировка, группировка и соединения прерываются, пока читают данные
+struct BudgetedStore<'a> {
+    store: &'a Store,
+    deadline: &'a Deadline,
+}
+
+impl<'a> QueryableDataset<'a> for BudgetedStore<'a> {
+    type InternalTerm = Term;
+    type Error = DatasetError;
+
+    fn internal_quads_for_pattern(
+        &self,
+        subject: Option<&Term>,
+        predicate: Option<&Term>,
+        object: Option<&Term>,
+        graph_name: Option<Option<&Term>>,
+    ) -> impl Iterator<Item = Result<InternalQuad<Term>, DatasetError>> + use<'a> {
+        self.quads(subject, predicate, object, graph_name)
+            .unwrap_or_else(|e| Box::new(std::iter::once(Err(e))))
+    }
+
+    fn internal_named_graphs(&self) -> impl Iterator<Item = Result<Term, DatasetError>> + use<'a> {
+        self.store
+            .named_graphs()
+            .map(|graph| graph.map(Term::from).map_err(|e| DatasetError(e.to_string())))
+    }
+
+    fn contains_internal_graph_name(&self, graph_name: &Term) -> Result<bool, DatasetError> {
+        let contains = match graph_name {
+            Term::NamedNode(node) => self.store.contains_named_graph(node),
+            Term::BlankNode(node) => self.store.contains_named_graph(node),
+            _ => Ok(false),
+        };
+        contains.map_err(|e| DatasetError(e.to_string()))
+    }
+
+    fn internalize_term(&self, term: Term) -> Result<Term, DatasetError> {
+        self.deadline.check().map_err(DatasetError)?;
+        Ok(term)
+    }
+
+    fn externalize_term(&self, term: Term) -> Result<Term, DatasetError> {
+        Ok(term)
+    }
+}
+
+type Quads<'a> = Box<dyn Iterator<Item = Result<InternalQuad<Term>, DatasetError>> + 'a>;
+
+impl<'a> BudgetedStore<'a> {
+    /// `graph_name`: `Some(None)` — граф по умолчанию, `None` — любой именованный граф
+    fn quads(
+        &self,
+        subject: Option<&Term>,
+        predicate: Option<&Term>,
+        object: Option<&Term>,
+        graph_name: Option<Option<&Term>>,
+    ) -> Result<Quads<'a>, DatasetError> {
+        self.deadline.check().map_err(DatasetError)?;
+        // Литерал в позиции субъекта или графа, не-IRI в позиции предиката — совпадений нет
+        let subject = match subject.map(TermRef::from) {
+            None => None,
+            Some(TermRef::NamedNode(node)) => Some(NamedOrBlankNodeRef::from(node)),
+            Some(TermRef::BlankNode(node)) => Some(NamedOrBlankNodeRef::from(node)),
+            Some(_) => return Ok(Box::new(std::iter::empty())),
+        };
+        let predicate = match predicate.map(TermRef::from) {
+            None => None,
+            Some(TermRef::NamedNode(node)) => Some(node),
+            Some(_) => return Ok(Box::new(std::iter::empty())),
+        };
+        let graph = match graph_name.map(|g| g.map(TermRef::from)) {
+            None => None,
+            Some(None) => Some(GraphNameRef::DefaultGraph),
+            Some(Some(TermRef::NamedNode(node))) => Some(GraphNameRef::from(node)),
+            Some(Some(TermRef::BlankNode(node))) => Some(GraphNameRef::from(node)),
+            Some(Some(_)) => return Ok(Box::new(std::iter::empty())),
+        };
+        let any_named = graph_name.is_none();
+        let deadline = self.deadline;
+        Ok(Box::new(
+            self.store
+                .quads_for_pattern(subject, predicate, object.map(TermRef::from), graph)
+                .filter(move |quad| !(any_named && quad.as_ref().is_ok_and(|q| q.graph_name.is_default_graph())))
+                .map(move |quad| {
+                    deadline.check().map_err(DatasetError)?;
+                    let Quad {
+                        subject,
+                        predicate,
+                        object,
+                        graph_name,
+                    } = quad.map_err(|e| DatasetError(e.to_string()))?;
+                    Ok(InternalQuad {
+                        subject: subject.into(),
+                        predicate: predicate.into(),
+                        object,
+                        graph_name: match graph_name {
+                            GraphName::NamedNode(node) => Some(node.into()),
+                            GraphName::BlankNode(node) => Some(node.into()),
+                            GraphName::DefaultGraph => None,
+                        },
+                    })
+                }),
+        ))
+    }
+}
+
+/// Ошибка вычисления; ошибка чтения (в том числе превышение бюджета) — своим сообщением
+fn evaluation_error(error: QueryEvaluationError) -> String {
+    match error {
+        QueryEvaluationError::Dataset(e) => e.to_string(),
+        e => format!("Ошибка вычисления запроса: {e}"),
+    }
+}
+
+/// Выполняет запрос только на чтение. Без FROM граф по умолчанию — объединение пакетов KB;
+/// SERVICE недоступен: сборка без HTTP-клиента, приложение работает оффлайн
+pub fn query(store: &Store, query: &str, limits: &QueryLimits) -> Result<QueryOutput, String> {
+    let deadline = Deadline::start(limits.time_budget_ms);
+    let mut prepared = match sparql::evaluator()?.parse_query(query) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            if sparql::evaluator()?.parse_update(query).is_ok() {
+                return Err("Запросы на изменение KB (INSERT, DELETE, LOAD…) запрещены".to_string());
+            }
+            return Err(e.to_string());
+        }
+    };
+    if prepared.dataset().is_default_dataset() {
+        prepared.dataset_mut().set_default_graph_as_union();
+    }
+
+    let dataset = BudgetedStore {
+        store,
+        deadline: &deadline,
+    };
+    let results = prepared.on_queryable_dataset(dataset).execute().map_err(evaluation_error)?;
+    let output = match results {
+        QueryResults::Solutions(solutions) => {
+            let serializer = QueryResultsSerializer::from_format(QueryResultsFormat::Json);
+            let mut writer = serializer
+                .serialize_solutions_to_writer(Vec::new(), solutions.variables().to_vec())
+                .map_err(|e| e.to_string())?;
+            let (mut count, mut truncated) = (0, false);
+            for solution in solutions {
+                deadline.check()?;
+                let solution = solution.map_err(evaluation_error)?;
+                if count == limits.max_results {
+                    truncated = true;
+                    break;
+                }
+                writer.serialize(&solution).map_err(|e| e.to_string())?;
+                count += 1;
+            }
+            let result = writer.finish().map_err(|e| e.to_string())?;
+            Ok(QueryOutput {
+                kind: QueryKind::Select,
+                format: "json",
+                result: String::from_utf8(result).map_err(|e| e.to_string())?,
+                count,
+                truncated,
+            })
+        }
+        QueryResults::Boolean(value) => {
+            let result = QueryResultsSerializer::from_format(QueryResultsFormat::Json)
+                .serialize_boolean_to_writer(Vec::new(), value)
+                .map_err(|e| e.to_string())?;
+            Ok(QueryOutput {
+                kind: QueryKind::Ask,
+                format: "json",
+                result: String::from_utf8(result).map_err(|e| e.to_string())?,
+                count: 1,
+                truncated: false,
+            })
+        }
+        QueryResults::Graph(triples) => {
+            let mut serializer = RdfSerializer::from_format(RdfFormat::Turtle);
+            for (prefix, iri) in PREFIXES {
+                serializer = serializer.with_prefix(*prefix, *iri).map_err(|e| e.to_string())?;
+            }
+            let mut writer = serializer.for_writer(Vec::new());
+            let (mut count, mut truncated) = (0, false);
+            for triple in triples {
+                deadline.check()?;
+                let triple = triple.map_err(evaluation_error)?;
+                if count == limits.max_results {
+                    truncated = true;
+                    break;
+                }
+                writer.serialize_triple(&triple).map_err(|e| e.to_string())?;
+                count += 1;
+            }
+            let result = writer.finish().map_err(|e| e.to_string())?;
+            Ok(QueryOutput {
+                kind: QueryKind::Graph,
+                format: "turtle",
+                result: String::from_utf8(result).map_err(|e| e.to_string())?,
+                count,
+                truncated,
+            })
+        }
+    };
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::packs;
+
+    const DATA: &str = r#"
+        @prefix fus: <http://onto16.org/fus/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+        fus:A rdfs:label "А" .
+        fus:B rdfs:label "Б" .
+        fus:C rdfs:label "В" .
+    "#;
+
+    fn setup() -> Store {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/query.ttl", DATA.as_bytes()).unwrap();
+        store
+    }
+
+    #[test]
+    fn update_is_rejected() {
+        let store = setup();
+        let error = query(&store, "DELETE WHERE { ?s ?p ?o }", &QueryLimits::default()).unwrap_err();
+        assert!(error.contains("запрещены"), "{error}");
+        assert_eq!(store.len().unwrap(), 3);
+    }
+
+    #[test]
+    fn max_results_truncates() {
+        let store = setup();
+        let limits = QueryLimits {
+            max_results: 2,
+            ..Default::default()
+        };
+        let output = query(&store, "SELECT ?s WHERE { ?s ?p ?o }", &limits).unwrap();
+        assert_eq!(output.kind, QueryKind::Select);
+        assert_eq!((output.count, output.truncated), (2, true));
+
+        let output = query(&store, "CONSTRUCT WHERE { ?s ?p ?o }", &QueryLimits::default()).unwrap();
+        assert_eq!((output.kind, output.count, output.truncated), (QueryKind::Graph, 3, false));
+    }
+
+    #[test]
+    fn budget_stops_evaluation() {
+        let store = setup();
+        let limits = QueryLimits {
+            time_budget_ms: 0,
+            ..Default::default()
+        };
+        // ASK и COUNT не выдают решений по одному: бюджет срабатывает при чтении хранилища
+        for sparql in ["ASK { ?s ?p ?o }", "SELECT (COUNT(*) AS ?n) WHERE { ?s ?p ?o }"] {
+            let error = query(&store, sparql, &limits).unwrap_err();
+            assert!(error.contains("бюджет"), "{sparql}: {error}");
+        }
+    }
+}
@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Логический вывод RDFS/OWL-lite: прямая цепочка до неподвижной точки.
+// Правила: rdfs:subClassOf, rdfs:subPropertyOf, rdfs:domain, rdfs:range, owl:inverseOf.
+// Выведенные триплеты пишутся в отдельный граф, пакеты KB не меняются.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use oxigraph::model::vocab::{rdf, rdfs};
+use oxigraph::model::{GraphNameRef, NamedNode, NamedNodeRef, NamedOrBlankNode, Term, Triple};
+use oxigraph::store::Store;
+use serde::Serialize;
+
+/// Граф выведенных триплетов. Вне пространства пакетов: не перечисляется в `list_packs`
+/// и не попадает в манифест, но входит в объединение графов, которое видят запросы
+pub const INFERRED_GRAPH: &str = "http://onto16.org/inferred/rdfs";
+
+const OWL_INVERSE_OF: NamedNodeRef<'_> = NamedNodeRef::new_unchecked("http://www.w3.org/2002/07/owl#inverseOf");
+
+/// Итог вывода
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct Entailment {
+    pub triples: usize,                         // всего выведено
+    pub iterations: usize,                      // проходов до неподвижной точки
+    pub by_rule: BTreeMap<&'static str, usize>, // выведено каждым правилом
+}
+
+pub fn inferred_graph() -> NamedNode {
+    NamedNode::new_unchecked(INFERRED_GRAPH)
+}
+
+/// Схема, собранная из известных триплетов на текущем проходе
+#[derive(Default)]
+struct Schema {
+    super_classes: HashMap<Term, Vec<Term>>,
+    super_properties: HashMap<NamedNode, Vec<NamedNode>>,
+    domains: HashMap<NamedNode, Vec<Term>>,
+    ranges: HashMap<NamedNode, Vec<Term>>,
+    inverses: HashMap<NamedNode, Vec<NamedNode>>,
+}
+
+impl Schema {
+    fn collect(triples: &HashSet<Triple>) -> Self {
+        let mut schema = Self::default();
+        for t in triples {
+            let subject = Term::from(t.subject.clone());
+            let as_property = |term: &Term| match term {
+                Term::NamedNode(node) => Some(node.clone()),
+                _ => None,
+            };
+            if t.predicate == rdfs::SUB_CLASS_OF {
+                schema.super_classes.entry(subject).or_default().push(t.object.clone());
+                continue;
+            }
+            let (NamedOrBlankNode::NamedNode(property), object) = (&t.subject, &t.object) else {
+                continue;
+            };
+            if t.predicate == rdfs::SUB_PROPERTY_OF {
+                if let Some(parent) = as_property(object) {
+                    schema.super_properties.entry(property.clone()).or_default().push(parent);
+                }
+            } else if t.predicate == rdfs::DOMAIN {
+                schema.domains.entry(property.clone()).or_default().push(object.clone());
+            } else if t.predicate == rdfs::RANGE {
+                schema.ranges.entry(property.clone()).or_default().push(object.clone());
+            } else if t.predicate == OWL_INVERSE_OF {
+                if let Some(inverse) = as_property(object) {
+                    schema.inverses.entry(property.clone()).or_default().push(inverse);
+                }
+            }
+        }
+        schema
+    }
+
+    /// Следствия одного триплета за один шаг: транзитивность и наследование
+    /// получаются повторными проходами
+    fn apply(&self, t: &Triple, derive: &mut impl FnMut(&'static str, Triple)) {
+        let type_of = |node: NamedOrBlankNode, class: &Term| Triple::new(node, rdf::TYPE, class.clone());
+
+        // rdfs9 (тип наследуется по иерархии классов) и rdfs11 (транзитивность subClassOf)
+        if t.predicate == rdf::TYPE || t.predicate == rdfs::SUB_CLASS_OF {
+            for parent in self.super_classes.get(&t.object).into_iter().flatten() {
+                let rule = if t.predicate == rdf::TYPE { "rdfs9" } else { "rdfs11" };
+                derive(rule, Triple::new(t.subject.clone(), t.predicate.clone(), parent.clone()));
+            }
+        }
+        // rdfs5: транзитивность subPropertyOf
+        if t.predicate == rdfs::SUB_PROPERTY_OF {
+            if let Term::NamedNode(property) = &t.object {
+                for parent in self.super_properties.get(property).into_iter().flatten() {
+                    derive("rdfs5", Triple::new(t.subject.clone(), rdfs::SUB_PROPERTY_OF, parent.clone()));
+                }
+            }
+        }
+        // rdfs7: утверждение наследуется надсвойством
+        for parent in self.super_properties.get(&t.predicate).into_iter().flatten() {
+            derive("rdfs7", Triple::new(t.subject.clone(), parent.clone(), t.object.clone()));
+        }
+        // rdfs2: тип субъекта по области определения
+        for class in self.domains.get(&t.predicate).into_iter().flatten() {
+            derive("rdfs2", type_of(t.subject.clone(), class));
+        }
+        // rdfs3 и owl:inverseOf применимы только к объектам-ресурсам, не к литералам
+        let Ok(object) = NamedOrBlankNode::try_from(t.object.clone()) else {
+            return;
+        };
+        for class in self.ranges.get(&t.predicate).into_iter().flatten() {
+            derive("rdfs3", type_of(object.clone(), class));
+        }
+        if t.predicate == OWL_INVERSE_OF {
+            if let NamedOrBlankNode::NamedNode(property) = &t.subject {
+                // Обратность симметрична
+                derive("owl-inv1", Triple::new(object.clone(), OWL_INVERSE_OF, property.clone()));
+            }
+        }
+        for inverse in self.inverses.get(&t.predicate).into_iter().flatten() {
+            derive("owl-inv2", Triple::new(object.clone(), inverse.clone(), t.subject.clone()));
+        }
+    }
+}
+
+/// Пересчитывает граф `INFERRED_GRAPH` по всем остальным графам хранилища.
+/// Вывод не порождает новых ресурсов, поэтому неподвижная точка всегда достигается;
+/// результат не зависит от порядка загрузки пакетов
+pub fn materialize(store: &Store) -> Result<Entailment, String> {
+    let graph = inferred_graph();
+    let mut known = HashSet::new();
+    for quad in store.iter() {
+        let quad = quad.map_err(|e| e.to_string())?;
+        if quad.graph_name.as_ref() != GraphNameRef::NamedNode(graph.as_ref()) {
+            known.insert(Triple::from(quad));
+        }
+    }
+
+    let mut report = Entailment::default();
+    let mut inferred = Vec::new();
+    loop {
+        report.iterations += 1;
+        let schema = Schema::collect(&known);
+        let mut derived = HashSet::new();
+        // Порядок обхода фиксирован: правило, первым выведшее триплет, не зависит от хеширования
+        let mut ordered: Vec<&Triple> = known.iter().collect();
+        ordered.sort_by_cached_key(|t| t.to_string());
+        for t in ordered {
+            schema.apply(t, &mut |rule, triple| {
+                if !known.contains(&triple) && derived.insert(triple) {
+                    *report.by_rule.entry(rule).or_default() += 1;
+                }
+            });
+        }
+        if derived.is_empty() {
+            break;
+        }
+        inferred.extend(derived.iter().cloned());
+        known.extend(derived);
+    }
+    report.triples = inferred.len();
+
+    store.remove_named_graph(&graph).map_err(|e| e.to_string())?;
+    store.insert_named_graph(&graph).map_err(|e| e.to_string())?;
+    store
+        .extend(inferred.into_iter().map(|t| t.in_graph(graph.clone())))
+        .map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::packs;
+    use oxigraph::model::{GraphName, Quad};
+
+    const SCHEMA: &str = r#"
+        @prefix ex: <http://example.org/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix owl: <http://www.w3.org/2002/07/owl#> .
+
+        ex:Dog rdfs:subClassOf ex:Mammal .
+        ex:Mammal rdfs:subClassOf ex:Animal .
+        ex:hasPuppy rdfs:subPropertyOf ex:hasChild .
+        ex:hasChild rdfs:domain ex:Parent ; rdfs:range ex:Animal ; owl:inverseOf ex:hasParent .
+
+        ex:Rex a ex:Dog ; ex:hasPuppy ex:Bim ; ex:hasChild "не ресурс" .
+    "#;
+
+    fn ex(name: &str) -> NamedNode {
+        NamedNode::new_unchecked(format!("http://example.org/{name}"))
+    }
+
+    fn setup() -> (Store, Entailment) {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/schema.ttl", SCHEMA.as_bytes()).unwrap();
+        let report = materialize(&store).unwrap();
+        (store, report)
+    }
+
+    fn inferred(store: &Store, subject: &str, predicate: impl Into<NamedNode>, object: impl Into<Term>) -> bool {
+        let quad = Quad::new(ex(subject), predicate, object, inferred_graph());
+        store.contains(&quad).unwrap()
+    }
+
+    #[test]
+    fn each_rule_derives_its_triple() {
+        let (store, report) = setup();
+        // rdfs11 и rdfs9 (через два шага иерархии)
+        assert!(inferred(&store, "Dog", rdfs::SUB_CLASS_OF, ex("Animal")));
+        assert!(inferred(&store, "Rex", rdf::TYPE, ex("Mammal")));
+        assert!(inferred(&store, "Rex", rdf::TYPE, ex("Animal")));
+        // rdfs7
+        assert!(inferred(&store, "Rex", ex("hasChild"), ex("Bim")));
+        // rdfs2 и rdfs3 (литерал в позиции объекта типа не получает)
+        assert!(inferred(&store, "Rex", rdf::TYPE, ex("Parent")));
+        assert!(inferred(&store, "Bim", rdf::TYPE, ex("Animal")));
+        // owl:inverseOf: симметрия и обратное утверждение
+        assert!(inferred(&store, "hasParent", OWL_INVERSE_OF, ex("hasChild")));
+        assert!(inferred(&store, "Bim", ex("hasParent"), ex("Rex")));
+
+        for rule in ["rdfs2", "rdfs3", "rdfs7", "rdfs9", "rdfs11", "owl-inv1", "owl-inv2"] {
+            assert!(report.by_rule.contains_key(rule), "{rule}: {:?}", report.by_rule);
+        }
+        assert_eq!(report.triples, report.by_rule.values().sum::<usize>());
+    }
+
+    #[test]
+    fn inferred_triples_stay_out_of_packs() {
+        let (store, report) = setup();
+        let pack = NamedNode::new_unchecked(format!("{}test/schema.ttl", packs::KB_BASE_IRI));
+        let in_pack = store.quads_for_pattern(None, None, None, Some(pack.as_ref().into())).count();
+        assert_eq!(in_pack, 9);
+        let in_inferred = store
+            .quads_for_pattern(None, None, None, Some(inferred_graph().as_ref().into()))
+            .count();
+        assert_eq!(in_inferred, report.triples);
+        assert_eq!(store.len().unwrap(), in_pack + in_inferred);
+        assert!(store
+            .quads_for_pattern(None, None, None, Some(GraphName::DefaultGraph.as_ref()))
+            .next()
+            .is_none());
+
+        // Повторный вывод заменяет граф, а не дописывает его
+        assert_eq!(materialize(&store).unwrap().triples, report.triples);
+        assert_eq!(store.len().unwrap(), in_pack + in_inferred);
+    }
+
+    #[test]
+    fn transitive_chain_reaches_fixpoint() {
+        let store = Store::new().unwrap();
+        // Цепочка C0 ⊂ C1 ⊂ … ⊂ C20, замкнутая в цикл: он тоже не должен зацикливать вывод
+        let data: String = (0..=20)
+            .map(|i| format!("{} {} {} .\n", ex(&format!("C{i}")), rdfs::SUB_CLASS_OF, ex(&format!("C{}", (i + 1) % 21))))
+            .collect();
+        packs::load_pack(&store, "test/chain.ttl", data.as_bytes()).unwrap();
+        let report = materialize(&store).unwrap();
+        // 21 класс в цикле: каждый — подкласс каждого, включая себя
+        assert_eq!(report.triples, 21 * 21 - 21);
+        assert!(report.iterations <= 21, "{}", report.iterations);
+        assert!(inferred(&store, "C0", rdfs::SUB_CLASS_OF, ex("C20")));
+    }
+}
@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Правила вывода, записанные в KB: ресурсы `rule:Rule` с телом SPARQL CONSTRUCT.
+// Эксперт предметной области добавляет правило в Turtle, не трогая Rust;
+// движок выполняет правила до неподвижной точки вместе с выводом RDFS/OWL-lite.
+
+use std::collections::HashSet;
+
+use oxigraph::model::{NamedNode, Term, Triple};
+use oxigraph::sparql::QueryResults;
+use oxigraph::store::Store;
+use serde::Serialize;
+
+use crate::iri::{self, Iri};
+use crate::knowledge::reasoner::{self, Entailment};
+use crate::sparql;
+
+/// Граф триплетов, выведенных правилами
+pub const RULES_GRAPH: &str = "http://onto16.org/inferred/rules";
+
+/// Предел проходов: правило, порождающее новые пустые узлы, иначе не остановится
+pub const MAX_ITERATIONS: usize = 16;
+
+const RULES_QUERY: &str = r#"
+SELECT ?rule ?body (SAMPLE(?l) AS ?label) WHERE {
+    ?rule a rule:Rule ;
+        rule:construct ?body .
+    OPTIONAL { ?rule rdfs:label ?l }
+    FILTER NOT EXISTS { ?rule rule:enabled false }
+}
+GROUP BY ?rule ?body
+ORDER BY ?rule
+"#;
+
+/// Правило из KB
+struct Rule {
+    id: Iri,
+    label: Option<String>,
+    body: String,
+}
+
+/// Что вывело правило: триплеты в записи CURIE, в порядке вывода
+#[derive(Serialize, Debug, Clone)]
+pub struct RuleRun {
+    pub rule: Iri,
+    pub label: Option<String>,
+    pub derived: Vec<String>,
+}
+
+/// Итог выполнения правил
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct RuleReport {
+    pub iterations: usize,
+    pub fixpoint: bool, // false — остановлено по `MAX_ITERATIONS`, вывод неполон
+    pub rules: Vec<RuleRun>,
+}
+
+impl RuleReport {
+    /// Всего выведено правилами
+    pub fn derived(&self) -> usize {
+        self.rules.iter().map(|run| run.derived.len()).sum()
+    }
+}
+
+fn rules_graph() -> NamedNode {
+    NamedNode::new_unchecked(RULES_GRAPH)
+}
+
+fn load_rules(store: &Store) -> Result<Vec<Rule>, String> {
+    let mut rules = Vec::new();
+    for row in sparql::select(store, RULES_QUERY, &[])? {
+        let (Some(id), Some(body)) = (sparql::iri(&row, "rule"), sparql::literal(&row, "body")) else {
+            continue;
+        };
+        rules.push(Rule {
+            id,
+            label: sparql::literal(&row, "label"),
+            body,
+        });
+    }
+    Ok(rules)
+}
+
+/// Выполняет тело правила по объединению графов
+fn construct(store: &Store, rule: &Rule) -> Result<Vec<Triple>, String> {
+    let mut prepared = sparql::evaluator()?
+        .parse_query(&rule.body)
+        .map_err(|e| format!("{}: {}", rule.id, e))?;
+    prepared.dataset_mut().set_default_graph_as_union();
+    match prepared.on_store(store).execute().map_err(|e| format!("{}: {}", rule.id, e))? {
+        QueryResults::Graph(triples) => triples
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("{}: {}", rule.id, e)),
+        _ => Err(format!("{}: тело правила должно быть CONSTRUCT-запросом", rule.id)),
+    }
+}
+
+fn term_text(term: &Term) -> String {
+    match term {
+        Term::NamedNode(node) => iri::compact(node.as_str()),
+        other => other.to_string(),
+    }
+}
+
+fn triple_text(t: &Triple) -> String {
+    format!(
+        "{} {} {}",
+        term_text(&t.subject.clone().into()),
+        iri::compact(t.predicate.as_str()),
+        term_text(&t.object)
+    )
+}
+
+/// Полный вывод по хранилищу: RDFS/OWL-lite, затем правила KB до неподвижной точки.
+/// После каждого прохода, добавившего триплеты, вывод RDFS пересчитывается,
+/// чтобы следующий проход видел иерархию классов и для выведенных фактов.
+/// Граф правил пересобирается с нуля: после замены пакетов или добавления события
+/// устаревших выводов не остаётся.
+pub fn infer(store: &Store) -> Result<(Entailment, RuleReport), String> {
+    let graph = rules_graph();
+    store.remove_named_graph(&graph).map_err(|e| e.to_string())?;
+    let mut entailment = reasoner::materialize(store)?;
+
+    let rules = load_rules(store)?;
+    let mut report = RuleReport {
+        rules: rules
+            .iter()
+            .map(|rule| RuleRun {
+                rule: rule.id.clone(),
+                label: rule.label.clone(),
+                derived: Vec::new(),
+            })
+            .collect(),
+        ..Default::default()
+    };
+    if rules.is_empty() {
+        report.fixpoint = true;
+        return Ok((entailment, report));
+    }
+
+    store.insert_named_graph(&graph).map_err(|e| e.to_string())?;
+    while report.iterations < MAX_ITERATIONS {
+        report.iterations += 1;
+        let mut batch = HashSet::new();
+        for (rule, run) in rules.iter().zip(report.rules.iter_mut()) {
+            for triple in construct(store, rule)? {
+                let known = store
+                    .quads_for_pattern(
+                        Some(triple.subject.as_ref()),
+                        Some(triple.predicate.as_ref()),
+                        Some(triple.object.as_ref()),
+                        None,
+                    )
+                    .next()
+                    .is_some();
+                if !known && !batch.contains(&triple) {
+                    run.derived.push(triple_text(&triple));
+                    batch.insert(triple);
+                }
+            }
+        }
+        if batch.is_empty() {
+            report.fixpoint = true;
+            break;
+        }
+        store
+            .extend(batch.into_iter().map(|t| t.in_graph(graph.clone())))
+            .map_err(|e| e.to_string())?;
+        entailment = reasoner::materialize(store)?;
+    }
+    Ok((entailment, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::load_knowledge_base;
+    use crate::knowledge::events::{self, EventRecord};
+
+    #[test]
+    fn state_surveillance_flags_separateness() {
+        let kb = load_knowledge_base().unwrap();
+        let event = EventRecord {
+            id: Iri::new("http://example.org/event/surveillance").unwrap(),
+            label: "Франция вводит тотальную слежку".to_string(),
+            occurred_at: Some("2026-05-01".to_string()),
+            actors: vec![Iri::from_curie("wd:Q142")],
+            actions: vec![Iri::from_curie("pert:TotalSurveillance")],
+            triggered_by: Vec::new(),
+            cycles: Vec::new(),
+        };
+        events::add_event(&kb.store, &event).unwrap();
+        let (_, report) = infer(&kb.store).unwrap();
+
+        let run = report
+            .rules
+            .iter()
+            .find(|run| run.rule == Iri::from_curie("rule:StateSurveillance"))
+            .unwrap();
+        assert_eq!(
+            run.derived,
+            vec!["http://example.org/event/surveillance rule:flagsInvariant fus:SeparatenessConnection".to_string()]
+        );
+        let flagged = kb
+            .store
+            .quads_for_pattern(
+                Some(event.id.as_node().into()),
+                Some(Iri::from_curie("rule:flagsInvariant").as_node()),
+                Some(Iri::from_curie("fus:SeparatenessConnection").term().as_ref()),
+                Some(rules_graph().as_ref().into()),
+            )
+            .count();
+        assert_eq!(flagged, 1);
+    }
+}
@@ -0,0 +1,373 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Валидатор KB: правила формы в духе SHACL поверх хранилища.
+// Каждое правило — SPARQL-запрос, возвращающий нарушителей (?graph ?subject [?value]);
+// граф указывает, в каком файле искать ошибку. Выведенные графы не учитываются:
+// по rdfs:range любая ссылка «имела бы» нужный тип.
+
+use oxigraph::store::Store;
+use serde::Serialize;
+
+use crate::iri::Iri;
+use crate::knowledge::packs;
+use crate::sparql;
+
+/// Серьёзность нарушения (как `sh:Violation` / `sh:Warning`)
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Violation, // KB нельзя выпускать
+    Warning,   // допустимо, но стоит исправить
+}
+
+/// Правило формы
+pub struct Shape {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub message: &'static str,
+    query: &'static str,
+}
+
+/// Нарушение правила: файл, ресурс и, если есть, значение-нарушитель
+#[derive(Serialize, Debug, Clone)]
+pub struct ShapeViolation {
+    pub shape: &'static str,
+    pub severity: Severity,
+    pub file: String,    // пакет (путь относительно kb/) или IRI графа
+    pub subject: String, // CURIE ресурса
+    pub value: Option<String>,
+    pub message: &'static str,
+}
+
+/// Правила формы ФУС-онтологии
+pub const SHAPES: &[Shape] = &[
+    // === Инварианты и уровни ===
+    Shape {
+        id: "invariant-level",
+        severity: Severity::Violation,
+        message: "инвариант без fus:belongsToLevel",
+        query: r#"SELECT ?graph ?subject WHERE {
+            GRAPH ?graph { ?subject a fus:Invariant }
+            FILTER NOT EXISTS { ?subject fus:belongsToLevel ?level }
+        }"#,
+    },
+    Shape {
+        id: "invariant-level-target",
+        severity: Severity::Violation,
+        message: "fus:belongsToLevel указывает не на fus:FUS_Level",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject fus:belongsToLevel ?value }
+            FILTER NOT EXISTS { ?value a fus:FUS_Level }
+        }"#,
+    },
+    Shape {
+        id: "label",
+        severity: Severity::Warning,
+        message: "ресурс без rdfs:label",
+        query: r#"SELECT ?graph ?subject WHERE {
+            VALUES ?class { fus:FUS_Level fus:Invariant fus:Temperament pert:PerturbationType pert:ResponseAction onto:Phase temporal:Cycle }
+            GRAPH ?graph { ?subject a ?class }
+            FILTER NOT EXISTS { ?subject rdfs:label ?label }
+        }"#,
+    },
+    // === Возмущения ===
+    Shape {
+        id: "affects-invariant-target",
+        severity: Severity::Violation,
+        message: "pert:affectsInvariant указывает на неопределённый инвариант",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject pert:affectsInvariant ?value }
+            FILTER NOT EXISTS { ?value a fus:Invariant }
+        }"#,
+    },
+    Shape {
+        id: "trigger-leads-to-target",
+        severity: Severity::Violation,
+        message: "pert:leadsTo указывает на неопределённый тип возмущения",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject pert:leadsTo ?value }
+            FILTER NOT EXISTS { ?value a pert:PerturbationType }
+        }"#,
+    },
+    Shape {
+        id: "suggested-action-target",
+        severity: Severity::Violation,
+        message: "pert:suggestedAction указывает на неопределённое действие",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject pert:suggestedAction ?value }
+            FILTER NOT EXISTS { ?value a pert:ResponseAction }
+        }"#,
+    },
+    Shape {
+        id: "trigger-term-language",
+        severity: Severity::Violation,
+        message: "триггерный термин без языкового тега (@ru, @en)",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            VALUES ?property { fus:triggerTerm pert:triggerTerm }
+            GRAPH ?graph { ?subject ?property ?value }
+            FILTER(!isLiteral(?value) || LANG(?value) = "")
+        }"#,
+    },
+    // === Профили субъектов ===
+    Shape {
+        id: "temperament-target",
+        severity: Severity::Violation,
+        message: "fus:mapsToTemperament указывает на неопределённый темперамент",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject fus:mapsToTemperament ?value }
+            FILTER NOT EXISTS { ?value a fus:Temperament }
+        }"#,
+    },
+    Shape {
+        id: "subject-level-target",
+        severity: Severity::Violation,
+        message: "fus:hasFUSLevel указывает не на fus:FUS_Level",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject fus:hasFUSLevel ?value }
+            FILTER NOT EXISTS { ?value a fus:FUS_Level }
+        }"#,
+    },
+    Shape {
+        id: "subject-invariant-target",
+        severity: Severity::Violation,
+        message: "fus:activeInvariant указывает на неопределённый инвариант",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject fus:activeInvariant ?value }
+            FILTER NOT EXISTS { ?value a fus:Invariant }
+        }"#,
+    },
+    // === Контекстуальный индекс и онтогенез ===
+    Shape {
+        id: "event-date",
+        severity: Severity::Warning,
+        message: "событие без temporal:occurredAt",
+        query: r#"SELECT ?graph ?subject WHERE {
+            GRAPH ?graph { ?subject a temporal:Event }
+            FILTER NOT EXISTS { ?subject temporal:occurredAt ?date }
+        }"#,
+    },
+    Shape {
+        id: "event-link-target",
+        severity: Severity::Violation,
+        message: "связь событий указывает на неопределённое событие",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            VALUES ?property { temporal:ledTo temporal:analogousTo temporal:triggeredBy }
+            GRAPH ?graph { ?subject ?property ?value }
+            FILTER NOT EXISTS { ?value a temporal:Event }
+        }"#,
+    },
+    Shape {
+        id: "event-cycle-target",
+        severity: Severity::Violation,
+        message: "temporal:partOfCycle указывает не на цикл temporal:Cycle",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject temporal:partOfCycle ?value }
+            FILTER NOT EXISTS { ?value a temporal:Cycle }
+        }"#,
+    },
+    Shape {
+        id: "event-action-target",
+        severity: Severity::Violation,
+        message: "temporal:describesAction указывает не на тип возмущения, триггер или действие реконструкции",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject temporal:describesAction ?value }
+            FILTER NOT EXISTS { ?value a pert:PerturbationType }
+            FILTER NOT EXISTS { ?value a pert:Trigger }
+            FILTER NOT EXISTS { ?value a pert:ResponseAction }
+        }"#,
+    },
+    Shape {
+        id: "phase-transition-target",
+        severity: Severity::Violation,
+        message: "onto:leadsTo указывает не на фазу онтогенеза",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject onto:leadsTo ?value }
+            FILTER NOT EXISTS { ?value a onto:Phase }
+        }"#,
+    },
+    Shape {
+        id: "transition-phases",
+        severity: Severity::Violation,
+        message: "переход onto:Transition без фазы onto:from или onto:to",
+        query: r#"SELECT ?graph ?subject WHERE {
+            GRAPH ?graph { ?subject a onto:Transition }
+            FILTER (NOT EXISTS { ?subject onto:from ?from . ?from a onto:Phase }
+                || NOT EXISTS { ?subject onto:to ?to . ?to a onto:Phase })
+        }"#,
+    },
+    Shape {
+        id: "transition-temperament-target",
+        severity: Severity::Violation,
+        message: "onto:preferredForTemperament указывает не на темперамент fus:Temperament",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject onto:preferredForTemperament ?value }
+            FILTER NOT EXISTS { ?value a fus:Temperament }
+        }"#,
+    },
+    Shape {
+        id: "temperament-transition-gap",
+        severity: Severity::Warning,
+        message: "фаза ветвится по темпераменту, но для этого темперамента предпочтительного перехода нет",
+        query: r#"SELECT DISTINCT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?guided a onto:Transition ; onto:from ?subject ; onto:preferredForTemperament ?any }
+            ?value a fus:Temperament .
+            FILTER NOT EXISTS {
+                ?transition a onto:Transition ;
+                    onto:from ?subject ;
+                    onto:preferredForTemperament ?value .
+            }
+        }"#,
+    },
+    Shape {
+        id: "transition-trigger",
+        severity: Severity::Warning,
+        message: "переход onto:Transition без onto:triggeredBy никогда не сработает",
+        query: r#"SELECT ?graph ?subject WHERE {
+            GRAPH ?graph { ?subject a onto:Transition }
+            FILTER NOT EXISTS { ?subject onto:triggeredBy ?trigger }
+        }"#,
+    },
+    // === Коллективная память ===
+    Shape {
+        id: "memory-forbids-target",
+        severity: Severity::Violation,
+        message: "memory:forbidsAction указывает на неопределённое действие реконструкции",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject memory:forbidsAction ?value }
+            FILTER NOT EXISTS { ?value a pert:ResponseAction }
+        }"#,
+    },
+    Shape {
+        id: "memory-invariant-target",
+        severity: Severity::Violation,
+        message: "memory:activatesInvariant указывает на неопределённый инвариант",
+        query: r#"SELECT ?graph ?subject ?value WHERE {
+            GRAPH ?graph { ?subject memory:activatesInvariant ?value }
+            FILTER NOT EXISTS { ?value a fus:Invariant }
+        }"#,
+    },
+    // === Правила вывода ===
+    Shape {
+        id: "rule-construct",
+        severity: Severity::Violation,
+        message: "правило без rule:construct или с несколькими телами",
+        query: r#"SELECT ?graph ?subject WHERE {
+            GRAPH ?graph { ?subject a rule:Rule }
+            OPTIONAL { ?subject rule:construct ?body }
+        }
+        GROUP BY ?graph ?subject
+        HAVING (COUNT(?body) != 1)"#,
+    },
+];
+
+fn term_text(iri: Option<Iri>, literal: Option<String>) -> Option<String> {
+    iri.map(|iri| iri.curie())
+        .or_else(|| literal.map(|value| format!("\"{}\"", value)))
+}
+
+/// Проверяет хранилище всеми правилами; нарушения — по файлу, затем по ресурсу
+pub fn validate(store: &Store) -> Result<Vec<ShapeViolation>, String> {
+    let mut violations = Vec::new();
+    for shape in SHAPES {
+        let rows = sparql::select_asserted(store, shape.query, &[]).map_err(|e| format!("{}: {}", shape.id, e))?;
+        for row in rows {
+            let (Some(graph), Some(subject)) = (sparql::iri(&row, "graph"), sparql::iri(&row, "subject")) else {
+                continue;
+            };
+            violations.push(ShapeViolation {
+                shape: shape.id,
+                severity: shape.severity,
+                file: packs::pack_name(graph.as_str()).unwrap_or(graph.as_str()).to_string(),
+                subject: subject.curie(),
+                value: term_text(sparql::iri(&row, "value"), sparql::literal(&row, "value")),
+                message: shape.message,
+            });
+        }
+    }
+    violations.sort_by(|a, b| (&a.file, &a.subject, a.shape).cmp(&(&b.file, &b.subject, b.shape)));
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLEAN: &str = r#"
+        @prefix fus: <http://onto16.org/fus/> .
+        @prefix pert: <http://onto16.org/pert/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+        fus:Level a fus:FUS_Level ; rdfs:label "Уровень" .
+        fus:Invariant_Kept a fus:Invariant ; rdfs:label "Инвариант" ; fus:belongsToLevel fus:Level .
+        fus:Calm a fus:Temperament ; rdfs:label "Спокойный" .
+        pert:Leak a pert:PerturbationType ; rdfs:label "Утечка" ; pert:affectsInvariant fus:Invariant_Kept .
+        fus:Reader fus:mapsToTemperament fus:Calm .
+    "#;
+
+    fn check(broken: &str) -> Vec<ShapeViolation> {
+        let store = Store::new().unwrap();
+        packs::load_pack(&store, "test/clean.ttl", CLEAN.as_bytes()).unwrap();
+        if !broken.is_empty() {
+            let data = format!(
+                "@prefix fus: <http://onto16.org/fus/> .\n@prefix pert: <http://onto16.org/pert/> .\n\
+                 @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n{broken}"
+            );
+            packs::load_pack(&store, "test/broken.ttl", data.as_bytes()).unwrap();
+        }
+        validate(&store).unwrap()
+    }
+
+    fn single(violations: &[ShapeViolation]) -> (&'static str, Severity, &str, &str, Option<&str>) {
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        let v = &violations[0];
+        (v.shape, v.severity, v.file.as_str(), v.subject.as_str(), v.value.as_deref())
+    }
+
+    #[test]
+    fn clean_store_has_no_violations() {
+        assert!(check("").is_empty());
+    }
+
+    #[test]
+    fn invariant_without_level() {
+        let violations = check(r#"fus:Invariant_Lost a fus:Invariant ; rdfs:label "Без уровня" ."#);
+        assert_eq!(
+            single(&violations),
+            ("invariant-level", Severity::Violation, "test/broken.ttl", "fus:Invariant_Lost", None)
+        );
+    }
+
+    #[test]
+    fn dangling_affects_invariant() {
+        let violations = check("pert:Leak pert:affectsInvariant fus:Invariant_Missing .");
+        assert_eq!(
+            single(&violations),
+            (
+                "affects-invariant-target",
+                Severity::Violation,
+                "test/broken.ttl",
+                "pert:Leak",
+                Some("fus:Invariant_Missing")
+            )
+        );
+    }
+
+    #[test]
+    fn undefined_temperament() {
+        let violations = check("fus:Reader fus:mapsToTemperament fus:Missing .");
+        assert_eq!(
+            single(&violations),
+            ("temperament-target", Severity::Violation, "test/broken.ttl", "fus:Reader", Some("fus:Missing"))
+        );
+    }
+
+    #[test]
+    fn missing_label_is_a_warning() {
+        let violations = check("fus:Quiet a fus:Temperament .");
+        assert_eq!(
+            single(&violations),
+            ("label", Severity::Warning, "test/broken.ttl", "fus:Quiet", None)
+        );
+    }
+}
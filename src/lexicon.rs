@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Триггерный лексикон: токенизация и стемминг (Snowball) для русского и английского.
+// Термины живут в KB (`pert:triggerTerm`, `fus:triggerTerm` с языковыми тегами),
+// здесь — только правила сопоставления.
+
+use std::sync::OnceLock;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// Язык токена или термина
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    Ru,
+    En,
+}
+
+impl Lang {
+    /// Язык по тегу литерала (`ru`, `en-GB`…); неизвестные теги — `None`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.split('-').next().unwrap_or("").to_lowercase().as_str() {
+            "ru" => Some(Lang::Ru),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    /// Язык по письменности: кириллица → русский, иначе английский
+    pub fn detect(word: &str) -> Self {
+        if word.chars().any(|c| ('\u{0400}'..='\u{04FF}').contains(&c)) {
+            Lang::Ru
+        } else {
+            Lang::En
+        }
+    }
+
+    /// Стеммер языка: создаётся один раз на процесс
+    fn stemmer(self) -> &'static Stemmer {
+        static RU: OnceLock<Stemmer> = OnceLock::new();
+        static EN: OnceLock<Stemmer> = OnceLock::new();
+        match self {
+            Lang::Ru => RU.get_or_init(|| Stemmer::create(Algorithm::Russian)),
+            Lang::En => EN.get_or_init(|| Stemmer::create(Algorithm::English)),
+        }
+    }
+}
+
+/// Разбивает текст на слова в нижнем регистре (ё → е)
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase().replace('ё', "е"))
+        .collect()
+}
+
+/// Основы слов фразы; язык — по тегу, иначе по письменности каждого слова
+pub fn stem_phrase(phrase: &str, lang: Option<Lang>) -> Vec<String> {
+    tokenize(phrase)
+        .into_iter()
+        .map(|word| {
+            let lang = lang.unwrap_or_else(|| Lang::detect(&word));
+            lang.stemmer().stem(&word).into_owned()
+        })
+        .collect()
+}
+
+/// Триггерный термин KB с основами, вычисленными один раз при загрузке KB
+#[derive(Debug, Clone)]
+pub struct TriggerTerm {
+    pub text: String,
+    pub lang: Option<Lang>,
+    stems: Vec<String>,
+}
+
+impl TriggerTerm {
+    pub fn new(text: String, lang: Option<Lang>) -> Self {
+        let stems = stem_phrase(&text, lang);
+        Self { text, lang, stems }
+    }
+}
+
+/// Текст события, приведённый к последовательности основ
+pub struct StemmedText {
+    stems: Vec<String>,
+}
+
+impl StemmedText {
+    pub fn new(text: &str) -> Self {
+        Self {
+            stems: stem_phrase(text, None),
+        }
+    }
+
+    /// Встречается ли термин KB в тексте: все основы термина подряд.
+    /// Сравниваются целые слова, поэтому "ai" не совпадает с "said" или "paint".
+    pub fn contains(&self, term: &TriggerTerm) -> bool {
+        let phrase = term.stems.as_slice();
+        !phrase.is_empty() && self.stems.windows(phrase.len()).any(|w| w == phrase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(text: &str, term: &str, lang: Option<Lang>) -> bool {
+        StemmedText::new(text).contains(&TriggerTerm::new(term.to_string(), lang))
+    }
+
+    #[test]
+    fn whole_words_only() {
+        assert!(!matches("He said the paint was dry", "ai", Some(Lang::En)));
+        assert!(matches("New AI rules were said to be strict", "ai", Some(Lang::En)));
+    }
+
+    #[test]
+    fn russian_inflections() {
+        assert!(matches("Новые запреты для школ", "запрет", Some(Lang::Ru)));
+        assert!(matches("Введён запрет на ИИ", "запреты", Some(Lang::Ru)));
+        assert!(matches("Запреты ИИ в школах", "запрет ИИ", None));
+    }
+
+    #[test]
+    fn phrase_is_contiguous() {
+        assert!(matches("total surveillance of citizens", "total surveillance", Some(Lang::En)));
+        assert!(!matches("total and constant surveillance", "total surveillance", Some(Lang::En)));
+    }
+}
@@ -3,31 +3,78 @@
 //
 // NoemaFeed Mobile — Ontological News Companion
 // Core engine: FUS diagnostics, perturbation analysis, reconstruction
+//
+// Нативный API — модули `core` и `engine`; WASM-экспорт ниже — тонкая обёртка над `engine`.
+
+use std::cell::RefCell;
 
 use wasm_bindgen::prelude::*;
-use oxigraph::store::Store;
 use serde::{Deserialize, Serialize};
 use base64::{Engine as _, engine::general_purpose};
 
+pub mod context;
+pub mod core;
+pub mod engine;
+pub mod iri;
+pub mod knowledge;
+mod lexicon;
+mod sparql;
+pub mod trace;
+
+use crate::core::astro::{self, Profile};
+use engine::{AnalysisMode, NewsEvent};
+use trace::Trace;
+
 // === Типы данных ===
 
+/// Необязательные параметры анализа (JSON, все поля необязательны)
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AnalysisOptions {
+    #[serde(flatten)]
+    pub analysis: engine::AnalysisOptions,
+    pub birth_date: Option<String>, // "ГГГГ-ММ-ДД": полный астропрофиль вместо года
+}
+
+/// Одно нарушение из ранжированного списка
 #[derive(Serialize, Deserialize)]
-pub struct EventInput {
-    pub title: String,
-    pub content: Option<String>,
-    pub source_url: Option<String>,
-    pub published_at: Option<String>,
+pub struct Violation {
+    pub invariant: iri::Iri, // в JSON — CURIE
+    pub label: String,
+    pub fus_level: String,
+    pub severity: f32,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct AnalysisResult {
     pub fus_level: String,
     pub affected_invariant: String,
+    pub violations: Vec<Violation>,
     pub perturbation_type: String,
     pub mode: String,
     pub temperament_hint: String,
+    pub personalized: bool, // false — нейтральный режим, профиль не задан
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconstruction: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<core::normative::Suggestion>, // с решением нормативного фильтра и цитатами памяти
+    #[serde(default)]
+    pub analogs: Vec<context::analogs::Analog>, // исторические аналоги, от самого похожего
+    #[serde(default)]
+    pub potentially_unique: bool, // аналогов в контекстуальном индексе нет
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<context::cycles::CyclePosition>, // период цикла и место события в нём
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<context::ontogenesis::PhaseTrack>, // фазы онтогенеза акторов и циклов события
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outlook: Vec<context::ontogenesis::TransitionOutlook>, // выход из фаз для темперамента; `transition: null` — пробел KB
     pub summary: String,
     pub digest: String,
+    pub knowledge: Vec<String>, // пакеты KB анализа: «fus/core.ttl@1.0.0»
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kb_problems: Vec<String>, // проблемы манифеста: «dynamics/ontogenesis.ttl: версия 1.1.0 ниже требуемой 1.2.0»
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Trace>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -39,148 +86,326 @@ pub struct ShareDigest {
     temperament_hint: String,
 }
 
-// === Глобальное состояние ===
-static mut STORE: Option<Store> = None;
+// === Движок по умолчанию для свободных функций ===
+// `analyze_event`, `query_kb`, `get_astro_profile` работают с KB, загруженной `init_kb()`,
+// и без неё возвращают ошибку; `get_plain_summary` без неё берёт общую метку инварианта.
+// Веб-приложение пользуется экземпляром `NoemaEngine` и его методами.
+thread_local! {
+    static DEFAULT_ENGINE: RefCell<Option<engine::NoemaEngine>> = const { RefCell::new(None) };
+}
 
-// === Инициализация баз знаний ===
-#[wasm_bindgen]
-pub fn init_kb() -> Result<(), JsValue> {
-    let store = Store::new().map_err(|e| JsValue::from_str(&e.to_string()))?;
+fn js_error(e: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
 
-    // Загрузка TTL-файлов из kb/
-    let files = [
-        include_str!("../kb/fus/core.ttl"),
-        include_str!("../kb/fus/structural.ttl"),
-        include_str!("../kb/fus/archetypal.ttl"),
-        include_str!("../kb/fus/identity.ttl"),
-        include_str!("../kb/fus/existential.ttl"),
-        include_str!("../kb/dynamics/perturbations.ttl"),
-        include_str!("../kb/dynamics/context-index.ttl"),
-        include_str!("../kb/subjects/astro-profiles.ttl"),
-    ];
+fn with_default_kb<T>(f: impl FnOnce(&engine::KnowledgeBase) -> Result<T, JsValue>) -> Result<T, JsValue> {
+    DEFAULT_ENGINE.with(|cell| {
+        let engine = cell.borrow();
+        let engine = engine.as_ref().ok_or(JsValue::from_str("KB not initialized"))?;
+        f(engine.kb().map_err(js_error)?)
+    })
+}
 
-    for ttl in files {
-        store
-            .load_from_reader(oxigraph::io::RdfFormat::Turtle, ttl.as_bytes())
-            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+/// Профиль по аргументам JS: полная дата рождения, иначе год; без них — нейтральный режим
+fn js_profile(birth_year: Option<u32>, birth_date: Option<&str>) -> Result<Profile, JsValue> {
+    match (birth_date, birth_year) {
+        (Some(date), _) => Profile::parse_date(date).map_err(|e| JsValue::from_str(&e)),
+        (None, Some(year)) => Ok(Profile::BirthYear(year as i32)),
+        (None, None) => Ok(Profile::Neutral),
     }
+}
 
-    unsafe {
-        STORE = Some(store);
+fn parse_options(options_json: Option<String>) -> Result<AnalysisOptions, JsValue> {
+    match options_json {
+        Some(json) => serde_json::from_str(&json).map_err(js_error),
+        None => Ok(AnalysisOptions::default()),
     }
+}
 
+// === Инициализация баз знаний ===
+
+/// Загружает встроенные KB в движок по умолчанию (нейтральный профиль)
+#[wasm_bindgen]
+pub fn init_kb() -> Result<(), JsValue> {
+    let engine = engine::NoemaEngine::new(Profile::Neutral, engine::AnalysisOptions::default()).map_err(js_error)?;
+    DEFAULT_ENGINE.with(|cell| *cell.borrow_mut() = Some(engine));
     Ok(())
 }
 
 // === Основная функция анализа ===
 #[wasm_bindgen]
-pub fn analyze_event(input_json: &str, mode: &str, birth_year: u32) -> Result<JsValue, JsValue> {
-    let event: EventInput = serde_json::from_str(input_json)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+pub fn analyze_event(
+    input_json: &str,
+    mode: &str,
+    birth_year: Option<u32>,
+    options_json: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let event: NewsEvent = serde_json::from_str(input_json).map_err(js_error)?;
+    let options = parse_options(options_json)?;
+    let profile = js_profile(birth_year, options.birth_date.as_deref())?;
+
+    let scene = with_default_kb(|kb| {
+        engine::build_onto_scene(kb, &event, AnalysisMode::parse(mode), &profile, &options.analysis).map_err(js_error)
+    })?;
+    scene_to_json(&event, scene)
+}
 
-    let store = unsafe { STORE.as_ref().ok_or(JsValue::from_str("KB not initialized"))? };
+// === Экспорт: запросы к KB ===
 
-    // 1. Темперамент по году рождения
-    let temperament = determine_temperament(birth_year);
+fn parse_limits(limits_json: Option<String>) -> Result<knowledge::query::QueryLimits, JsValue> {
+    match limits_json {
+        Some(json) => serde_json::from_str(&json).map_err(js_error),
+        None => Ok(Default::default()),
+    }
+}
 
-    // 2. Диагностика ФУС (заглушка)
-    let (fus_level, invariant) = diagnose_fus_level(&event)?;
+/// SPARQL-запрос только на чтение к KB движка по умолчанию.
+/// Результат — JSON: `kind` (select/ask/graph), `format` (json/turtle), `result`, `count`, `truncated`.
+/// Ограничения: `{"max_results": 1000, "time_budget_ms": 2000}`; бюджет проверяется при каждом
+/// чтении из хранилища, поэтому прерывает и ORDER BY, и агрегаты до первого решения
+#[wasm_bindgen]
+pub fn query_kb(sparql: &str, limits_json: Option<String>) -> Result<JsValue, JsValue> {
+    let limits = parse_limits(limits_json)?;
+    let output = with_default_kb(|kb| knowledge::query::query(&kb.store, sparql, &limits).map_err(js_error))?;
+    Ok(JsValue::from_str(&serde_json::to_string(&output).map_err(js_error)?))
+}
 
-    // 3. Тип возмущения
-    let pert_type = match_perturbation_type(&event);
+// === Экспорт: экземпляр движка ===
 
-    // 4. Режим без лишних пробелов
-    let clean_mode = mode.trim();
-    let mode_label = match clean_mode {
-        "constructive" => "Конструктивный режим",
-        "analytical" => "Аналитический режим",
-        "critical" => "Критический режим",
-        _ => "Режим анализа",
+/// Движок с собственными KB, профилем и параметрами; на странице их может быть несколько
+#[wasm_bindgen(js_name = NoemaEngine)]
+pub struct JsNoemaEngine {
+    inner: engine::NoemaEngine,
+}
+
+#[wasm_bindgen(js_class = NoemaEngine)]
+impl JsNoemaEngine {
+    /// `new NoemaEngine(birthYear?, optionsJson?)`; `birth_date` в параметрах важнее года
+    #[wasm_bindgen(constructor)]
+    pub fn new(birth_year: Option<u32>, options_json: Option<String>) -> Result<JsNoemaEngine, JsValue> {
+        let options = parse_options(options_json)?;
+        let profile = js_profile(birth_year, options.birth_date.as_deref())?;
+        let inner = engine::NoemaEngine::new(profile, options.analysis).map_err(js_error)?;
+        Ok(JsNoemaEngine { inner })
+    }
+
+    /// Анализ события (JSON) с профилем и параметрами движка; результат — как у `analyze_event`
+    pub fn analyze(&self, input_json: &str, mode: &str) -> Result<JsValue, JsValue> {
+        let event: NewsEvent = serde_json::from_str(input_json).map_err(js_error)?;
+        let scene = self.inner.analyze(&event, AnalysisMode::parse(mode)).map_err(js_error)?;
+        scene_to_json(&event, scene)
+    }
+
+    /// Смена профиля; без аргументов — нейтральный режим
+    #[wasm_bindgen(js_name = setProfile)]
+    pub fn set_profile(&mut self, birth_year: Option<u32>, birth_date: Option<String>) -> Result<(), JsValue> {
+        self.inner.set_profile(js_profile(birth_year, birth_date.as_deref())?);
+        Ok(())
+    }
+
+    /// SPARQL-запрос только на чтение к KB этого движка; результат — как у `query_kb`
+    pub fn query(&self, sparql: &str, limits_json: Option<String>) -> Result<JsValue, JsValue> {
+        let output = self.inner.query(sparql, &parse_limits(limits_json)?).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&output).map_err(js_error)?))
+    }
+
+    /// Манифест KB (JSON): версии, даты и лицензии пакетов, совместимость с движком
+    pub fn manifest(&self) -> Result<JsValue, JsValue> {
+        let manifest = self.inner.manifest().map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&manifest).map_err(js_error)?))
+    }
+
+    /// Нарушения правил формы в загруженной KB (JSON: правило, файл, ресурс, значение)
+    pub fn validate(&self) -> Result<JsValue, JsValue> {
+        let violations = self.inner.validate().map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&violations).map_err(js_error)?))
+    }
+
+    /// Инварианты загруженной KB (JSON: IRI, метки и описания по языкам, уровень)
+    pub fn invariants(&self) -> Result<JsValue, JsValue> {
+        let invariants: Vec<_> = self.inner.invariants().map_err(js_error)?.iter().collect();
+        Ok(JsValue::from_str(&serde_json::to_string(&invariants).map_err(js_error)?))
+    }
+
+    /// Что вывели правила KB (JSON: правило, метка, выведенные триплеты; `fixpoint`)
+    pub fn rules(&self) -> Result<JsValue, JsValue> {
+        let report = &self.inner.kb().map_err(js_error)?.rules;
+        Ok(JsValue::from_str(&serde_json::to_string(report).map_err(js_error)?))
+    }
+
+    /// Добавление события в контекстуальный индекс:
+    /// `{"id": "wd:Q…", "label": "…", "occurred_at": "2026-02-01", "actors": [...], "actions": [...], "triggered_by": [...], "cycles": [...]}`.
+    /// Правила KB выполняются заново; результат — как у `rules`
+    #[wasm_bindgen(js_name = addEvent)]
+    pub fn add_event(&mut self, event_json: &str) -> Result<JsValue, JsValue> {
+        let event = serde_json::from_str(event_json).map_err(js_error)?;
+        let report = self.inner.add_event(event).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(report).map_err(js_error)?))
+    }
+
+    /// Фрейм следствий события (JSON как у `analyze`): дерево цепочек `temporal:ledTo`
+    /// с датами, типами возмущений и уровнями ФУС, проекция следующих уровней.
+    /// Глубина по умолчанию — 3; `null` — событие потенциально уникально
+    #[wasm_bindgen(js_name = effectFrame)]
+    pub fn effect_frame(&self, input_json: &str, max_depth: Option<u32>) -> Result<JsValue, JsValue> {
+        let event: NewsEvent = serde_json::from_str(input_json).map_err(js_error)?;
+        let depth = max_depth.map_or(context::frames::DEFAULT_DEPTH, |d| d as usize);
+        let frame = self.inner.effect_frame(&event, depth).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&frame).map_err(js_error)?))
+    }
+
+    /// Фрейм причин события (JSON как у `analyze`): триггеры и предшествующие события
+    /// от самого раннего, циклы, структурные предпосылки и объяснение по шагам.
+    /// Глубина по умолчанию — 3; `null` — событие потенциально уникально
+    #[wasm_bindgen(js_name = causeFrame)]
+    pub fn cause_frame(&self, input_json: &str, max_depth: Option<u32>) -> Result<JsValue, JsValue> {
+        let event: NewsEvent = serde_json::from_str(input_json).map_err(js_error)?;
+        let depth = max_depth.map_or(context::frames::DEFAULT_DEPTH, |d| d as usize);
+        let frame = self.inner.cause_frame(&event, depth).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&frame).map_err(js_error)?))
+    }
+
+    /// Фаза онтогенеза темы — актора или цикла (`"wd:Q142"`, `"temporal:TechnophobicCycle"`):
+    /// JSON с текущей фазой, числом входов в неё и историей переходов
+    pub fn phase(&self, subject: &str) -> Result<JsValue, JsValue> {
+        let subject = iri::Iri::new(subject).map_err(js_error)?;
+        let track = self.inner.phase(&subject).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&track).map_err(js_error)?))
+    }
+
+    /// Загруженные пакеты KB (JSON: имя, граф, число триплетов)
+    #[wasm_bindgen(js_name = listPacks)]
+    pub fn list_packs(&self) -> Result<JsValue, JsValue> {
+        let packs = self.inner.packs().map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&packs).map_err(js_error)?))
+    }
+
+    /// Загрузка пакета KB (Turtle) на устройстве, например `optional/collective-memory.ttl`
+    #[wasm_bindgen(js_name = loadPack)]
+    pub fn load_pack(&mut self, name: &str, data: &[u8]) -> Result<JsValue, JsValue> {
+        let info = self.inner.load_pack(name, data).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&info).map_err(js_error)?))
+    }
+
+    #[wasm_bindgen(js_name = replacePack)]
+    pub fn replace_pack(&mut self, name: &str, data: &[u8]) -> Result<JsValue, JsValue> {
+        let info = self.inner.replace_pack(name, data).map_err(js_error)?;
+        Ok(JsValue::from_str(&serde_json::to_string(&info).map_err(js_error)?))
+    }
+
+    /// Выгрузка пакета; `false` — такого пакета не было
+    #[wasm_bindgen(js_name = unloadPack)]
+    pub fn unload_pack(&mut self, name: &str) -> Result<bool, JsValue> {
+        self.inner.unload_pack(name).map_err(js_error)
+    }
+
+    /// Перезагрузка KB в новое хранилище
+    pub fn reload(&mut self) -> Result<(), JsValue> {
+        self.inner.reload().map_err(js_error)
+    }
+
+    /// Освобождение хранилища; до `reload` анализ недоступен
+    pub fn dispose(&mut self) {
+        self.inner.dispose();
+    }
+
+    /// Сводка из дайджеста `digest` результата анализа; метка инварианта — из KB этого движка
+    #[wasm_bindgen(js_name = plainSummary)]
+    pub fn plain_summary(&self, digest_b64: &str) -> Result<String, JsValue> {
+        plain_summary(self.inner.kb().ok(), digest_b64)
+    }
+
+    /// Астропрофиль по дате рождения (JSON); знаки и стихии — из KB этого движка
+    #[wasm_bindgen(js_name = astroProfile)]
+    pub fn astro_profile(&self, year: i32, month: u32, day: u32) -> Result<JsValue, JsValue> {
+        astro_profile(self.inner.kb().map_err(js_error)?, year, month, day)
+    }
+}
+
+// === Результат анализа в JSON ===
+
+fn scene_to_json(event: &NewsEvent, scene: engine::OntoScene) -> Result<JsValue, JsValue> {
+    let violations: Vec<Violation> = scene
+        .violations
+        .iter()
+        .map(|d| Violation {
+            invariant: d.violated_invariant.id.clone(),
+            label: d.violated_invariant.label.clone(),
+            fus_level: d.affected_level.name().to_string(),
+            severity: d.severity,
+        })
+        .collect();
+
+    let (fus_level, invariant) = match scene.primary() {
+        Some(d) => (d.affected_level.name().to_string(), d.violated_invariant.id.curie()),
+        None => ("Unknown".to_string(), "fus:Unknown".to_string()),
     };
+    let temperament = engine::temperament_hint(scene.temperament).to_string();
 
-    // 5. Сводка
-    let inv_label = if invariant == "fus:ObserverPosition" {
-        "Позиция наблюдателя"
+    // Онтологический дайджест
+    let event_id = if scene.event_id.is_empty() {
+        format!("event_{}", event.title.chars().take(20).collect::<String>())
     } else {
-        "Онтологический инвариант"
+        scene.event_id.clone()
     };
-
-    let summary = format!(
-        "[NoemaFeed • {}]\nНарушение: {} ({})\nТип возмущения: {}\nДля темперамента: {}",
-        mode_label, inv_label, fus_level, pert_type, temperament
-    );
-
-    // 6. Онтологический дайджест
     let digest_obj = ShareDigest {
-        event_id: format!("event_{}", &event.title[..event.title.len().min(20)]),
+        event_id,
         affected_invariant: invariant.clone(),
         fus_level: fus_level.clone(),
-        mode: clean_mode.to_string(),
+        mode: scene.mode.as_str().to_string(),
         temperament_hint: temperament.clone(),
     };
 
-    let digest_json = serde_json::to_string(&digest_obj)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let digest_json = serde_json::to_string(&digest_obj).map_err(js_error)?;
     let digest_b64 = general_purpose::STANDARD.encode(digest_json);
 
-    // 7. Формирование результата
     let result = AnalysisResult {
         fus_level,
         affected_invariant: invariant,
-        perturbation_type: pert_type,
-        mode: clean_mode.to_string(),
+        violations,
+        perturbation_type: scene.perturbation_type,
+        mode: scene.mode.as_str().to_string(),
         temperament_hint: temperament,
-        summary,
+        personalized: scene.temperament.is_some(),
+        reconstruction: scene.reconstruction,
+        suggestions: scene.suggestions,
+        analogs: scene.analogs.analogs,
+        potentially_unique: scene.analogs.potentially_unique,
+        cycles: scene.cycles,
+        phases: scene.phases,
+        outlook: scene.outlook,
+        summary: scene.summary,
         digest: digest_b64,
+        knowledge: scene.knowledge,
+        kb_problems: scene.kb_problems,
+        trace: scene.trace,
     };
 
-    let json_output = serde_json::to_string(&result)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
-
+    let json_output = serde_json::to_string(&result).map_err(js_error)?;
     Ok(JsValue::from_str(&json_output))
 }
 
-// === Вспомогательные функции ===
-
-fn determine_temperament(year: u32) -> String {
-    match year % 12 {
-        5 | 6 => "choleric",   // Змея, Лошадь → Огонь
-        3 | 4 => "sanguine",  // Кролик, Дракон → Дерево
-        7 | 8 => "phlegmatic", // Коза, Обезьяна → Земля
-        _ => "melancholic",
-    }.to_string()
-}
-
-fn diagnose_fus_level(_event: &EventInput) -> Result<(String, String), JsValue> {
-    Ok(("OntologicalLevel".to_string(), "fus:ObserverPosition".to_string()))
-}
-
-fn match_perturbation_type(event: &EventInput) -> String {
-    let title = event.title.to_lowercase();
-    if title.contains("ban") && title.contains("ai") {
-        "pert:RegulatoryPrecautionism".to_string()
-    } else if title.contains("manipulat") {
-        "pert:AlgorithmicManipulation".to_string()
-    } else {
-        "pert:GenericDisturbance".to_string()
-    }
-}
-
 // === Экспорт: восстановление сводки из дайджеста ===
+
+/// Сводка из дайджеста движка по умолчанию (`init_kb`); без KB метка инварианта общая
 #[wasm_bindgen]
 pub fn get_plain_summary(digest_b64: &str) -> Result<String, JsValue> {
-    let json_bytes = general_purpose::STANDARD
-        .decode(digest_b64)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    DEFAULT_ENGINE.with(|cell| {
+        let engine = cell.borrow();
+        plain_summary(engine.as_ref().and_then(|engine| engine.kb().ok()), digest_b64)
+    })
+}
 
-    let digest: ShareDigest = serde_json::from_slice(&json_bytes)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+fn plain_summary(kb: Option<&engine::KnowledgeBase>, digest_b64: &str) -> Result<String, JsValue> {
+    let json_bytes = general_purpose::STANDARD.decode(digest_b64).map_err(js_error)?;
+    let digest: ShareDigest = serde_json::from_slice(&json_bytes).map_err(js_error)?;
 
-    let inv_label = if digest.affected_invariant == "fus:ObserverPosition" {
-        "Позиция наблюдателя"
-    } else {
-        "Онтологический инвариант"
-    };
+    // Метка — из реестра инвариантов, если KB загружена
+    let inv_label = iri::Iri::new(&digest.affected_invariant)
+        .ok()
+        .zip(kb)
+        .and_then(|(id, kb)| kb.invariants.get(&id).map(|inv| inv.label.clone()))
+        .unwrap_or_else(|| "Онтологический инвариант".to_string());
 
     let mode_label = match digest.mode.as_str() {
         "constructive" => "Конструктивный режим",
@@ -190,7 +415,20 @@ pub fn get_plain_summary(digest_b64: &str) -> Result<String, JsValue> {
     };
 
     Ok(format!(
-        "[NoemaFeed • {}]\nУровень: {}\nИнвариант: {}\nТемперамент: {}",
-        mode_label, digest.fus_level, inv_label, digest.temperament_hint
+        "[NoemaFeed • {}]\nУровень: {}\nИнвариант: {}\n{}",
+        mode_label, digest.fus_level, inv_label, engine::personalization_line(&digest.temperament_hint)
     ))
+}
+
+// === Экспорт: астропрофиль по дате рождения ===
+
+/// Астропрофиль по KB движка по умолчанию; требует `init_kb()`
+#[wasm_bindgen]
+pub fn get_astro_profile(year: i32, month: u32, day: u32) -> Result<JsValue, JsValue> {
+    with_default_kb(|kb| astro_profile(kb, year, month, day))
+}
+
+fn astro_profile(kb: &engine::KnowledgeBase, year: i32, month: u32, day: u32) -> Result<JsValue, JsValue> {
+    let profile = astro::AstroProfile::from_date(&kb.store, year, month, day).map_err(js_error)?;
+    Ok(JsValue::from_str(&serde_json::to_string(&profile).map_err(js_error)?))
 }
\ No newline at end of file
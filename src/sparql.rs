@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// SPARQL-доступ к хранилищу баз знаний: запросы и разбор решений
+
+use oxigraph::model::{GraphName, NamedNode, NamedOrBlankNode, Term, Variable};
+use oxigraph::sparql::{QueryResults, QuerySolution, SparqlEvaluator};
+use oxigraph::store::Store;
+
+use crate::iri::{Iri, PREFIXES};
+use crate::knowledge::packs;
+
+/// Вычислитель запросов с префиксами реестра `iri::PREFIXES`
+pub fn evaluator() -> Result<SparqlEvaluator, String> {
+    let mut evaluator = SparqlEvaluator::new();
+    for (prefix, iri) in PREFIXES {
+        evaluator = evaluator
+            .with_prefix(*prefix, *iri)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(evaluator)
+}
+
+/// Выполняет SELECT-запрос с подстановкой значений переменных.
+/// Граф по умолчанию — объединение всех графов хранилища.
+/// Префиксы реестра `iri::PREFIXES` доступны в запросе без объявления;
+/// подставляемые переменные должны входить в проекцию SELECT.
+pub fn select(
+    store: &Store,
+    query: &str,
+    bindings: &[(&str, Term)],
+) -> Result<Vec<QuerySolution>, String> {
+    run_select(store, query, bindings, None)
+}
+
+/// Как `select`, но только по утверждённым данным пакетов, без выведенных графов:
+/// для проверок KB, которым вывод по rdfs:range и т.п. подсказал бы ответ
+pub fn select_asserted(
+    store: &Store,
+    query: &str,
+    bindings: &[(&str, Term)],
+) -> Result<Vec<QuerySolution>, String> {
+    let graphs = packs::list_packs(store)?
+        .into_iter()
+        .map(|pack| packs::graph_iri(&pack.name))
+        .collect::<Result<Vec<_>, _>>()?;
+    run_select(store, query, bindings, Some(graphs))
+}
+
+fn run_select(
+    store: &Store,
+    query: &str,
+    bindings: &[(&str, Term)],
+    graphs: Option<Vec<NamedNode>>,
+) -> Result<Vec<QuerySolution>, String> {
+    let mut prepared = evaluator()?.parse_query(query).map_err(|e| e.to_string())?;
+    // Каждый пакет KB — в своём именованном графе; запросы видят их объединение
+    match graphs {
+        None => prepared.dataset_mut().set_default_graph_as_union(),
+        Some(graphs) => {
+            let dataset = prepared.dataset_mut();
+            dataset.set_default_graph(graphs.iter().cloned().map(GraphName::from).collect());
+            dataset.set_available_named_graphs(graphs.into_iter().map(NamedOrBlankNode::from).collect());
+        }
+    }
+    for (name, value) in bindings {
+        let variable = Variable::new(*name).map_err(|e| e.to_string())?;
+        prepared = prepared.substitute_variable(variable, value.clone());
+    }
+
+    match prepared.on_store(store).execute().map_err(|e| e.to_string())? {
+        QueryResults::Solutions(solutions) => solutions
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string()),
+        _ => Err("Ожидался SELECT-запрос".to_string()),
+    }
+}
+
+/// IRI ресурса, связанного с переменной решения
+pub fn iri(solution: &QuerySolution, var: &str) -> Option<Iri> {
+    match solution.get(var) {
+        Some(Term::NamedNode(node)) => Some(node.clone().into()),
+        _ => None,
+    }
+}
+
+/// Лексическое значение литерала, связанного с переменной решения
+pub fn literal(solution: &QuerySolution, var: &str) -> Option<String> {
+    match solution.get(var) {
+        Some(Term::Literal(lit)) => Some(lit.value().to_string()),
+        _ => None,
+    }
+}
+
+/// Языковой тег литерала, связанного с переменной решения (`ru`, `en`…)
+pub fn language(solution: &QuerySolution, var: &str) -> Option<String> {
+    match solution.get(var) {
+        Some(Term::Literal(lit)) => lit.language().map(str::to_string),
+        _ => None,
+    }
+}
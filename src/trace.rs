@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MPL-2.0
+// Copyright © 2026 Maksim Zapevalov (max456im)
+//
+// Журнал вывода: на каких данных KB и каких шагах основан каждый вывод анализа
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Совпадение триггерного термина KB с текстом события
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TermMatch {
+    pub term: String,
+    pub resource: String, // CURIE ресурса, к которому привязан термин
+    pub property: String, // "pert:triggerTerm" или "fus:triggerTerm"
+}
+
+/// Трассировка анализа: совпавшие термины, использованные триплеты KB,
+/// вывод темперамента и шаги подсчёта серьёзности
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Trace {
+    pub matched_terms: Vec<TermMatch>,
+    pub kb_triples: Vec<String>,
+    pub temperament: Vec<String>,
+    pub scoring: Vec<String>,
+}
+
+impl Trace {
+    /// Записывает совпадение термина (без повторов)
+    pub fn term(&mut self, term: &str, resource: impl Display, property: &str) {
+        let m = TermMatch {
+            term: term.to_string(),
+            resource: resource.to_string(),
+            property: property.to_string(),
+        };
+        if !self.matched_terms.contains(&m) {
+            self.matched_terms.push(m);
+        }
+    }
+
+    /// Записывает использованный триплет KB в компактной записи (без повторов)
+    pub fn triple(&mut self, subject: impl Display, predicate: &str, object: impl Display) {
+        let triple = format!("{} {} {}", subject, predicate, object);
+        if !self.kb_triples.contains(&triple) {
+            self.kb_triples.push(triple);
+        }
+    }
+
+    pub fn temperament_step(&mut self, step: impl Into<String>) {
+        self.temperament.push(step.into());
+    }
+
+    pub fn scoring_step(&mut self, step: impl Into<String>) {
+        self.scoring.push(step.into());
+    }
+}
@@ -1,5 +1,5 @@
 // Загрузка WASM-модуля
-import init, { init_kb, analyze_event, get_plain_summary } from './pkg/noemafeed_mobile.js';
+import init, { NoemaEngine } from './pkg/noemafeed_mobile.js';
 
 // Регистрация Service Worker (относительный путь!)
 if ('serviceWorker' in navigator) {
@@ -29,12 +29,23 @@ const DEMO_EVENTS = [
 
 let currentEventIndex = 0;
 let currentMode = 'analytical'; // 'constructive', 'analytical', 'critical'
-let birthYear = 1993; // можно запросить у пользователя один раз
+// Год рождения из профиля; без профиля — нейтральный режим (undefined)
+const storedBirthYear = localStorage.getItem('birthYear');
+let birthYear = storedBirthYear ? Number(storedBirthYear) : undefined;
+let engine = null; // движок с собственной KB и профилем
+// Коллективная память: нормативный фильтр реконструкции; 'off' — не загружать пакет
+const collectiveMemoryEnabled = localStorage.getItem('collectiveMemory') !== 'off';
+const COLLECTIVE_MEMORY_PACK = 'optional/collective-memory.ttl';
 
 async function initApp() {
   try {
     await init();
-    await init_kb(); // инициализация баз знаний
+    engine = new NoemaEngine(birthYear); // инициализация баз знаний
+    await loadOptionalPacks();
+    const manifest = JSON.parse(engine.manifest());
+    if (!manifest.compatible) {
+      console.warn('KB неполна или несовместима с движком:', manifest);
+    }
     renderCurrentNews();
   } catch (e) {
     console.error('Ошибка инициализации:', e);
@@ -42,6 +53,23 @@ async function initApp() {
   }
 }
 
+// Необязательные пакеты KB рядом с приложением (`kb/optional/`); нет файла — анализ без них
+async function loadOptionalPacks() {
+  if (!collectiveMemoryEnabled) {
+    return;
+  }
+  try {
+    const response = await fetch(`./kb/${COLLECTIVE_MEMORY_PACK}`);
+    if (!response.ok) {
+      console.info('Коллективная память не найдена, нормативный фильтр отключён');
+      return;
+    }
+    engine.loadPack(COLLECTIVE_MEMORY_PACK, new Uint8Array(await response.arrayBuffer()));
+  } catch (e) {
+    console.warn('Не удалось загрузить коллективную память:', e);
+  }
+}
+
 function getCurrentEvent() {
   return DEMO_EVENTS[currentEventIndex];
 }
@@ -51,19 +79,42 @@ function renderCurrentNews() {
   document.getElementById('news-title').innerText = event.title;
 
   try {
-    // Передаём событие как JSON-строку (как ожидает analyze_event)
+    // Передаём событие как JSON-строку (как ожидает engine.analyze)
     const inputJson = JSON.stringify(event);
-    const resultJsValue = analyze_event(inputJson, currentMode, birthYear);
+    const resultJsValue = engine.analyze(inputJson, currentMode);
     const result = JSON.parse(resultJsValue.toString());
 
+    // Вторичные нарушения — всё, что ниже главного в ранжированном списке
+    const secondary = (result.violations || []).slice(1)
+      .map(v => `<li>${v.label} (${v.fus_level}, ${v.severity.toFixed(2)})</li>`)
+      .join('');
+
+    // Действия реконструкции после нормативного фильтра: отклонённые — с фреймом памяти
+    const verdicts = { allowed: '', flagged: ' — с оговоркой', vetoed: ' — отклонено' };
+    const suggestions = (result.suggestions || [])
+      .map(s => {
+        const frames = s.citations.map(c => c.label || c.frame).join(', ');
+        return `<li>${s.label || s.action}${verdicts[s.verdict]}${frames ? ` (${frames})` : ''}</li>`;
+      })
+      .join('');
+
     document.getElementById('news-analysis').innerHTML = `
       <p><strong>Уровень ФУС:</strong> ${result.fus_level}</p>
       <p><strong>Нарушённый инвариант:</strong> ${result.affected_invariant}</p>
+      ${secondary ? `<p><strong>Также нарушены:</strong></p><ul>${secondary}</ul>` : ''}
       <p><strong>Тип возмущения:</strong> ${result.perturbation_type}</p>
+      ${suggestions ? `<p><strong>Действия:</strong></p><ul>${suggestions}</ul>` : ''}
+      ${result.potentially_unique
+        ? '<p><strong>Контекст:</strong> потенциально уникальное событие — аналогов нет</p>'
+        : `<p><strong>Аналоги:</strong> ${result.analogs.map(a => `${a.label || a.event} (${a.occurred_at || '?'})`).join('; ')}</p>`}
+      ${(result.cycles || []).map(c => `<p><strong>Цикл:</strong> ${c.cycle.label || c.cycle.id}${c.cycle.period ? `, период ≈ ${c.cycle.period.toFixed(1)} г.` : ''}${c.next_expected ? `, следующее — около ${c.next_expected}` : ''}</p>`).join('')}
+      ${(result.phases || []).map(p => `<p><strong>Фаза (${p.subject_label || p.subject}):</strong> ${p.phase_label || p.phase}${p.visit > 1 ? ` — ${p.visit}-й раз` : ''}</p>`).join('')}
+      ${(result.outlook || []).map(o => `<p><strong>Прогноз (${o.temperament_label || o.temperament}):</strong> ${o.to ? `из «${o.from_label || o.from}» → «${o.to_label || o.to}»${o.regressive ? ' — регрессивная ветвь' : ''}` : `для выхода из «${o.from_label || o.from}» предпочтений нет`}</p>`).join('')}
       <p><strong>Режим:</strong> ${result.mode}</p>
-      <p><strong>Для темперамента:</strong> ${result.temperament_hint}</p>
+      <p><strong>Для темперамента:</strong> ${result.personalized ? result.temperament_hint : 'без персонализации'}</p>
       <hr>
       <pre>${result.summary}</pre>
+      <p class="kb-used"><small>Базы знаний: ${(result.knowledge || []).join(', ')}</small></p>
     `;
   } catch (e) {
     console.error('Ошибка анализа:', e);
@@ -104,7 +155,7 @@ document.getElementById('btn-copy')?.addEventListener('click', async () => {
   try {
     const event = getCurrentEvent();
     const inputJson = JSON.stringify(event);
-    const resultJsValue = analyze_event(inputJson, currentMode, birthYear);
+    const resultJsValue = engine.analyze(inputJson, currentMode);
     const result = JSON.parse(resultJsValue.toString());
     
     const textToCopy = `[NoemaFeed • ${result.mode}]\n${result.summary}`;
@@ -121,7 +172,7 @@ document.getElementById('btn-share')?.addEventListener('click', async () => {
   try {
     const event = getCurrentEvent();
     const inputJson = JSON.stringify(event);
-    const resultJsValue = analyze_event(inputJson, currentMode, birthYear);
+    const resultJsValue = engine.analyze(inputJson, currentMode);
     const result = JSON.parse(resultJsValue.toString());
     
     const url = `https://max456im.github.io/noemafeed-mobile/?digest=${encodeURIComponent(result.digest)}`;
@@ -137,15 +188,62 @@ document.getElementById('btn-share')?.addEventListener('click', async () => {
   }
 });
 
-// === Фреймы (заглушки) ===
-document.querySelector('.cause-frame-btn')?.addEventListener('click', openCauseFrame);
-document.querySelector('.effect-frame-btn')?.addEventListener('click', openEffectFrame);
+// === Фреймы ===
+document.getElementById('btn-cause')?.addEventListener('click', openCauseFrame);
+document.getElementById('btn-effect')?.addEventListener('click', openEffectFrame);
 
+// Фрейм причин: объяснение по шагам — циклы, предпосылки, цепочка событий
 function openCauseFrame() {
-  alert('Фрейм причин: анализ структурных и исторических предпосылок.');
+  try {
+    const frame = JSON.parse(engine.causeFrame(JSON.stringify(getCurrentEvent())).toString());
+    if (!frame) {
+      alert('Фрейм причин: событие потенциально уникально — в индексе нет аналогов.');
+      return;
+    }
+    document.getElementById('news-analysis').insertAdjacentHTML('beforeend', `
+      <hr>
+      <p><strong>Фрейм причин</strong>${frame.via_analog ? ' (по аналогу)' : ''}:</p>
+      <ol>${frame.explanation.map(line => `<li>${line}</li>`).join('')}</ol>
+      ${frame.truncated ? '<p><small>Предыстория продолжается за пределом глубины.</small></p>' : ''}
+    `);
+  } catch (e) {
+    console.error('Ошибка фрейма причин:', e);
+    alert('Не удалось построить фрейм причин');
+  }
 }
+
+// Узел фрейма следствий со вложенными следствиями
+function renderFrameNode(node) {
+  const meta = [node.occurred_at, ...node.perturbations, ...node.levels].filter(Boolean).join(', ');
+  const children = node.children.length
+    ? `<ul>${node.children.map(renderFrameNode).join('')}</ul>`
+    : '';
+  return `<li>${node.label || node.event}${meta ? ` <small>(${meta})</small>` : ''}${children}</li>`;
+}
+
 function openEffectFrame() {
-  alert('Фрейм следствий: проекция системных последствий.');
+  try {
+    const frame = JSON.parse(engine.effectFrame(JSON.stringify(getCurrentEvent())).toString());
+    if (!frame) {
+      alert('Фрейм следствий: событие потенциально уникально — в индексе нет аналогов.');
+      return;
+    }
+    const projection = frame.projection
+      .map(p => `<li>${p.level}: ${(p.likelihood * 100).toFixed(0)}%</li>`)
+      .join('');
+    document.getElementById('news-analysis').insertAdjacentHTML('beforeend', `
+      <hr>
+      <p><strong>Фрейм следствий</strong>${frame.via_analog ? ' (по аналогу)' : ''}:</p>
+      <ul>${renderFrameNode(frame.root)}</ul>
+      ${frame.truncated ? '<p><small>Цепочка продолжается за пределом глубины.</small></p>' : ''}
+      ${projection
+        ? `<p><strong>Вероятные следующие уровни ФУС:</strong></p><ul>${projection}</ul>`
+        : '<p>Следствий в индексе нет.</p>'}
+    `);
+  } catch (e) {
+    console.error('Ошибка фрейма следствий:', e);
+    alert('Не удалось построить фрейм следствий');
+  }
 }
 
 // === Инициализация ===
@@ -1,4 +1,4 @@
-const CACHE_NAME = 'noemafeed-v1';
+const CACHE_NAME = 'noemafeed-v2';
 const urlsToCache = [
   './',
   './index.html',
@@ -6,6 +6,7 @@ const urlsToCache = [
   './app.js',
   './pkg/noemafeed_mobile.js',
   './pkg/noemafeed_mobile_bg.wasm',
+  './kb/optional/collective-memory.ttl',
   './icons/icon-192.png',
   './icons/icon-512.png'
 ];